@@ -0,0 +1,110 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const APPT_CTR: Symbol = symbol_short!("APPT_CTR");
+
+/// How long before an appointment starts its access grant becomes valid, so
+/// the provider can prepare
+pub const ACCESS_LEAD_SECONDS: u64 = 86_400;
+
+/// How long after an appointment ends its access grant stays valid, so the
+/// provider can finish their notes
+pub const ACCESS_GRACE_SECONDS: u64 = 86_400;
+
+// ── Types ─────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AppointmentStatus {
+    Booked,
+    Confirmed,
+    Cancelled,
+    NoShow,
+}
+
+/// A slot booked between a patient and a provider
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Appointment {
+    pub id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub booked_by: Address,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub status: AppointmentStatus,
+    /// Expiry of the access grant created or extended on confirmation, or 0
+    /// if the provider already held a grant covering the appointment
+    pub grant_expires_at: u64,
+    /// Expiry of the provider's grant before confirmation extended it, or 0
+    /// if confirmation created the grant
+    pub prior_expires_at: u64,
+    /// Start of the provider's grant before confirmation extended it
+    pub prior_not_before: u64,
+    pub created_at: u64,
+}
+
+/// Internal store schema helpers
+pub fn appointment_key(appointment_id: u64) -> (Symbol, u64) {
+    (symbol_short!("APPT"), appointment_id)
+}
+
+pub fn patient_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("PAT_APPT"), patient.clone())
+}
+
+pub fn provider_key(provider: &Address) -> (Symbol, Address) {
+    (symbol_short!("PRV_APPT"), provider.clone())
+}
+
+// ======================== Appointments ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_appointment_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&APPT_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&APPT_CTR, &id);
+    id
+}
+
+pub fn get_appointment(env: &Env, appointment_id: u64) -> Option<Appointment> {
+    env.storage()
+        .persistent()
+        .get(&appointment_key(appointment_id))
+}
+
+pub fn save_appointment(env: &Env, appointment: &Appointment) {
+    env.storage()
+        .persistent()
+        .set(&appointment_key(appointment.id), appointment);
+}
+
+/// Store a new appointment and list it under its patient and provider
+pub fn book(env: &Env, appointment: &Appointment) {
+    save_appointment(env, appointment);
+
+    let mut ids = get_patient_appointments(env, &appointment.patient);
+    ids.push_back(appointment.id);
+    env.storage()
+        .persistent()
+        .set(&patient_key(&appointment.patient), &ids);
+
+    let mut ids = get_provider_appointments(env, &appointment.provider);
+    ids.push_back(appointment.id);
+    env.storage()
+        .persistent()
+        .set(&provider_key(&appointment.provider), &ids);
+}
+
+pub fn get_patient_appointments(env: &Env, patient: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&patient_key(patient))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn get_provider_appointments(env: &Env, provider: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&provider_key(provider))
+        .unwrap_or(Vec::new(env))
+}
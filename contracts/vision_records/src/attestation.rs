@@ -0,0 +1,83 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const ATT_CTR: Symbol = symbol_short!("ATT_CTR");
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// What an attestation asserts about its subject
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClaimType {
+    VisionScreeningPassed,
+    /// Visual acuity meets the driving standard
+    DrivingAcuityStandard,
+    ValidGlassesRx,
+    ValidContactLensRx,
+}
+
+/// A provider's claim about a patient, verifiable without record access
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Attestation {
+    pub id: u64,
+    pub issuer: Address,
+    pub subject: Address,
+    pub claim_type: ClaimType,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub revoked: bool,
+}
+
+/// Internal store schema helpers
+pub fn attestation_key(attestation_id: u64) -> (Symbol, u64) {
+    (symbol_short!("ATTEST"), attestation_id)
+}
+
+pub fn subject_key(subject: &Address) -> (Symbol, Address) {
+    (symbol_short!("SUB_ATTS"), subject.clone())
+}
+
+// ======================== Attestations ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_attestation_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&ATT_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&ATT_CTR, &id);
+    id
+}
+
+pub fn get_attestation(env: &Env, attestation_id: u64) -> Option<Attestation> {
+    env.storage()
+        .persistent()
+        .get(&attestation_key(attestation_id))
+}
+
+pub fn save_attestation(env: &Env, attestation: &Attestation) {
+    env.storage()
+        .persistent()
+        .set(&attestation_key(attestation.id), attestation);
+}
+
+/// Store a new attestation and list it under its subject
+pub fn issue(env: &Env, attestation: &Attestation) {
+    save_attestation(env, attestation);
+
+    let mut ids = get_subject_attestations(env, &attestation.subject);
+    ids.push_back(attestation.id);
+    env.storage()
+        .persistent()
+        .set(&subject_key(&attestation.subject), &ids);
+}
+
+pub fn get_subject_attestations(env: &Env, subject: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&subject_key(subject))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Whether the attestation is unrevoked and unexpired
+pub fn is_active(env: &Env, attestation: &Attestation) -> bool {
+    !attestation.revoked && attestation.expires_at > env.ledger().timestamp()
+}
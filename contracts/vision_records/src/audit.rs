@@ -0,0 +1,74 @@
+use crate::consent::PurposeOfUse;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+/// Largest page returned by the access log views
+pub const MAX_AUDIT_PAGE: u32 = 50;
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// Immutable record of a read — written once, never deleted
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReadAuditEntry {
+    pub record_id: u64,
+    pub patient: Address,
+    pub actor: Address,
+    pub purpose: PurposeOfUse,
+    pub timestamp: u64,
+}
+
+/// Internal store schema helpers
+pub fn audit_entry_key(patient: &Address, index: u64) -> (Symbol, Address, u64) {
+    (symbol_short!("AUDIT"), patient.clone(), index)
+}
+
+pub fn audit_count_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("AUD_CNT"), patient.clone())
+}
+
+// ======================== Read Audit Log ========================
+
+pub fn get_entry_count(env: &Env, patient: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&audit_count_key(patient))
+        .unwrap_or(0)
+}
+
+/// Append an entry to the patient's log. Entries are indexed from 0 in the
+/// order they were written.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn append_entry(env: &Env, entry: &ReadAuditEntry) {
+    let index = get_entry_count(env, &entry.patient);
+    env.storage()
+        .persistent()
+        .set(&audit_entry_key(&entry.patient, index), entry);
+    env.storage()
+        .persistent()
+        .set(&audit_count_key(&entry.patient), &(index + 1));
+}
+
+/// Up to `limit` entries of the patient's log, oldest first, starting at `offset`
+#[allow(clippy::arithmetic_side_effects)]
+pub fn get_entries(env: &Env, patient: &Address, offset: u64, limit: u32) -> Vec<ReadAuditEntry> {
+    let mut entries = Vec::new(env);
+    let count = get_entry_count(env, patient);
+    let limit = if limit > MAX_AUDIT_PAGE {
+        MAX_AUDIT_PAGE
+    } else {
+        limit
+    };
+
+    let mut index = offset;
+    while index < count && entries.len() < limit {
+        if let Some(entry) = env
+            .storage()
+            .persistent()
+            .get::<_, ReadAuditEntry>(&audit_entry_key(patient, index))
+        {
+            entries.push_back(entry);
+        }
+        index += 1;
+    }
+    entries
+}
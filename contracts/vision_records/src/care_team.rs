@@ -0,0 +1,106 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const CTEAM_CTR: Symbol = symbol_short!("CTEAM_CTR");
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// A named group of people a patient curates and can grant access to as a unit
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CareTeam {
+    pub id: u64,
+    pub patient: Address,
+    pub name: String,
+    pub members: Vec<Address>,
+    pub created_at: u64,
+}
+
+/// Internal store schema helpers
+pub fn team_key(team_id: u64) -> (Symbol, u64) {
+    (symbol_short!("CTEAM"), team_id)
+}
+
+pub fn patient_teams_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("PAT_TEAMS"), patient.clone())
+}
+
+pub fn member_teams_key(member: &Address) -> (Symbol, Address) {
+    (symbol_short!("MEM_TEAMS"), member.clone())
+}
+
+// ======================== Care Teams ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_team_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&CTEAM_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&CTEAM_CTR, &id);
+    id
+}
+
+pub fn get_team(env: &Env, team_id: u64) -> Option<CareTeam> {
+    env.storage().persistent().get(&team_key(team_id))
+}
+
+pub fn save_team(env: &Env, team: &CareTeam) {
+    env.storage().persistent().set(&team_key(team.id), team);
+}
+
+/// Create a team and list it under its patient
+pub fn create_team(env: &Env, team: &CareTeam) {
+    save_team(env, team);
+    push_id(env, &patient_teams_key(&team.patient), team.id);
+}
+
+pub fn get_patient_teams(env: &Env, patient: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&patient_teams_key(patient))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn get_member_teams(env: &Env, member: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&member_teams_key(member))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add a member. Returns false if they already belong to the team.
+pub fn add_member(env: &Env, team: &mut CareTeam, member: &Address) -> bool {
+    if team.members.contains(member) {
+        return false;
+    }
+    team.members.push_back(member.clone());
+    save_team(env, team);
+    push_id(env, &member_teams_key(member), team.id);
+    true
+}
+
+/// Remove a member. Returns false if they were not one.
+pub fn remove_member(env: &Env, team: &mut CareTeam, member: &Address) -> bool {
+    let index = match team.members.first_index_of(member) {
+        Some(index) => index,
+        None => return false,
+    };
+    team.members.remove(index);
+    save_team(env, team);
+
+    let key = member_teams_key(member);
+    let mut teams = get_member_teams(env, member);
+    if let Some(index) = teams.first_index_of(team.id) {
+        teams.remove(index);
+        env.storage().persistent().set(&key, &teams);
+    }
+    true
+}
+
+pub fn is_member(env: &Env, team_id: u64, user: &Address) -> bool {
+    get_team(env, team_id).is_some_and(|team| team.members.contains(user))
+}
+
+fn push_id(env: &Env, key: &(Symbol, Address), id: u64) {
+    let mut ids: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    ids.push_back(id);
+    env.storage().persistent().set(key, &ids);
+}
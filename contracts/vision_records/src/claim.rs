@@ -0,0 +1,108 @@
+use crate::{merkle, VisionRecord};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const CLAIM_CTR: Symbol = symbol_short!("CLAIM_CTR");
+
+// ── Types ─────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClaimStatus {
+    Submitted,
+    Approved,
+    Denied,
+}
+
+/// One billed procedure, backed by a record
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ClaimLine {
+    pub record_id: u64,
+    /// The record's Merkle leaf hash, so the claim can be checked against the
+    /// patient's chart without revealing the record's data hash
+    pub record_hash: BytesN<32>,
+    /// CPT-like procedure code
+    pub procedure_code: String,
+}
+
+/// An insurance claim for services documented in the patient's records
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Claim {
+    pub id: u64,
+    pub provider: Address,
+    pub patient: Address,
+    pub insurer: Address,
+    pub submitted_by: Address,
+    pub lines: Vec<ClaimLine>,
+    pub amount: i128,
+    pub status: ClaimStatus,
+    pub submitted_at: u64,
+    /// When the insurer approved or denied the claim, 0 until then
+    pub decided_at: u64,
+}
+
+/// Internal store schema helpers
+pub fn claim_key(claim_id: u64) -> (Symbol, u64) {
+    (symbol_short!("CLAIM"), claim_id)
+}
+
+pub fn provider_key(provider: &Address) -> (Symbol, Address) {
+    (symbol_short!("PRV_CLMS"), provider.clone())
+}
+
+pub fn insurer_key(insurer: &Address) -> (Symbol, Address) {
+    (symbol_short!("INS_CLMS"), insurer.clone())
+}
+
+pub fn patient_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("PAT_CLMS"), patient.clone())
+}
+
+// ======================== Claims ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_claim_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&CLAIM_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&CLAIM_CTR, &id);
+    id
+}
+
+pub fn get_claim(env: &Env, claim_id: u64) -> Option<Claim> {
+    env.storage().persistent().get(&claim_key(claim_id))
+}
+
+pub fn save_claim(env: &Env, claim: &Claim) {
+    env.storage().persistent().set(&claim_key(claim.id), claim);
+}
+
+/// Store a new claim and list it under its provider, insurer and patient
+pub fn submit(env: &Env, claim: &Claim) {
+    save_claim(env, claim);
+    push_id(env, provider_key(&claim.provider), claim.id);
+    push_id(env, insurer_key(&claim.insurer), claim.id);
+    push_id(env, patient_key(&claim.patient), claim.id);
+}
+
+fn push_id(env: &Env, key: (Symbol, Address), claim_id: u64) {
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(claim_id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+pub fn get_claims(env: &Env, key: (Symbol, Address)) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Whether a line still matches `record`, as currently stored
+pub fn line_matches(env: &Env, line: &ClaimLine, record: &VisionRecord) -> bool {
+    line.record_hash == merkle::leaf_hash(env, &merkle::leaf_of(record))
+}
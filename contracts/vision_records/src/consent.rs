@@ -0,0 +1,118 @@
+use soroban_sdk::{contracttype, symbol_short, vec, Address, Env, Symbol, Vec};
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// Why a record is being accessed, following the HL7 PurposeOfUse codes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PurposeOfUse {
+    Treatment,
+    Payment,
+    Operations,
+    Research,
+    Emergency,
+}
+
+/// A patient's standing consent policy, evaluated whenever access is granted
+/// and again on every audited read
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ConsentDirective {
+    pub patient: Address,
+    /// Purposes attached to grants that don't name their own
+    pub default_purposes: Vec<PurposeOfUse>,
+    /// Purposes no grant may carry and no read may declare
+    pub denied_purposes: Vec<PurposeOfUse>,
+    pub updated_at: u64,
+}
+
+/// Internal store schema helpers
+pub fn directive_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("CONSENT"), patient.clone())
+}
+
+/// Purposes of a patient-wide grant, kept apart from the `AccessGrant` so
+/// grants stored before purposes existed still decode
+pub fn grant_purposes_key(patient: &Address, grantee: &Address) -> (Symbol, Address, Address) {
+    (symbol_short!("GRT_PURP"), patient.clone(), grantee.clone())
+}
+
+// ======================== Consent Directives ========================
+
+/// The directive that applies until a patient sets their own: treatment,
+/// payment and operations are allowed, research is denied.
+pub fn default_directive(env: &Env, patient: &Address) -> ConsentDirective {
+    ConsentDirective {
+        patient: patient.clone(),
+        default_purposes: vec![
+            env,
+            PurposeOfUse::Treatment,
+            PurposeOfUse::Payment,
+            PurposeOfUse::Operations,
+        ],
+        denied_purposes: vec![env, PurposeOfUse::Research],
+        updated_at: 0,
+    }
+}
+
+pub fn get_directive(env: &Env, patient: &Address) -> ConsentDirective {
+    env.storage()
+        .persistent()
+        .get(&directive_key(patient))
+        .unwrap_or(default_directive(env, patient))
+}
+
+pub fn set_directive(env: &Env, directive: &ConsentDirective) {
+    env.storage()
+        .persistent()
+        .set(&directive_key(&directive.patient), directive);
+}
+
+/// Whether a directive's lists are usable: no purpose both allowed by
+/// default and denied
+pub fn is_valid_directive(directive: &ConsentDirective) -> bool {
+    for purpose in directive.default_purposes.iter() {
+        if directive.denied_purposes.contains(&purpose) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Whether the directive permits every one of `purposes`
+pub fn permits_all(directive: &ConsentDirective, purposes: &Vec<PurposeOfUse>) -> bool {
+    for purpose in purposes.iter() {
+        if directive.denied_purposes.contains(&purpose) {
+            return false;
+        }
+    }
+    true
+}
+
+// ======================== Grant Purposes ========================
+
+pub fn set_grant_purposes(
+    env: &Env,
+    patient: &Address,
+    grantee: &Address,
+    purposes: &Vec<PurposeOfUse>,
+) {
+    env.storage()
+        .persistent()
+        .set(&grant_purposes_key(patient, grantee), purposes);
+}
+
+pub fn remove_grant_purposes(env: &Env, patient: &Address, grantee: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&grant_purposes_key(patient, grantee));
+}
+
+/// The purposes a patient-wide grant may be exercised for. Grants made
+/// before purposes were recorded take the directive's defaults.
+pub fn get_grant_purposes(env: &Env, patient: &Address, grantee: &Address) -> Vec<PurposeOfUse> {
+    env.storage()
+        .persistent()
+        .get(&grant_purposes_key(patient, grantee))
+        .unwrap_or_else(|| get_directive(env, patient).default_purposes)
+}
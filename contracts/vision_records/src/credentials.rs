@@ -0,0 +1,70 @@
+use crate::Role;
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol};
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// Standing of a provider credential
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CredentialStatus {
+    Active,
+    Suspended,
+}
+
+/// A professional license attested by an accredited verifier
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProviderCredential {
+    pub provider: Address,
+    /// Hash of the license number; the number itself never goes on-chain
+    pub license_hash: BytesN<32>,
+    pub jurisdiction: String,
+    /// The clinical role this license qualifies the provider for
+    pub specialty: Role,
+    pub verifier: Address,
+    pub verified_at: u64,
+    pub expires_at: u64,
+    pub status: CredentialStatus,
+}
+
+/// Internal store schema helpers
+pub fn credential_key(provider: &Address) -> (Symbol, Address) {
+    (symbol_short!("CRED"), provider.clone())
+}
+
+// ======================== Credential Store ========================
+
+/// Roles whose clinical permissions depend on a valid credential
+pub fn is_clinical_role(role: &Role) -> bool {
+    *role == Role::Optometrist || *role == Role::Ophthalmologist
+}
+
+pub fn set_credential(env: &Env, credential: &ProviderCredential) {
+    env.storage()
+        .persistent()
+        .set(&credential_key(&credential.provider), credential);
+}
+
+pub fn get_credential(env: &Env, provider: &Address) -> Option<ProviderCredential> {
+    env.storage().persistent().get(&credential_key(provider))
+}
+
+/// Whether `provider` holds an active, unexpired credential for `specialty`
+pub fn has_valid_credential(env: &Env, provider: &Address, specialty: &Role) -> bool {
+    match get_credential(env, provider) {
+        Some(credential) => {
+            credential.status == CredentialStatus::Active
+                && credential.specialty == *specialty
+                && credential.expires_at > env.ledger().timestamp()
+        }
+        None => false,
+    }
+}
+
+/// Whether `provider` holds an active, unexpired credential for any specialty
+pub fn has_active_credential(env: &Env, provider: &Address) -> bool {
+    match get_credential(env, provider) {
+        Some(credential) => has_valid_credential(env, provider, &credential.specialty),
+        None => false,
+    }
+}
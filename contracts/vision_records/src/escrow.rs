@@ -0,0 +1,161 @@
+use crate::RecordType;
+use soroban_sdk::{contracttype, symbol_short, token, Address, Env, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const ESC_CTR: Symbol = symbol_short!("ESC_CTR");
+
+/// Longest an escrow may wait for its record before only a refund is left
+/// (one year)
+pub const MAX_TIMEOUT_SECONDS: u64 = 365 * 24 * 60 * 60;
+
+// ── Types ─────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EscrowStatus {
+    Held,
+    /// A matching record was added; the payment awaits `release_escrow`
+    Matched,
+    Released,
+    Refunded,
+}
+
+/// A patient's payment for a service, held by the contract until the
+/// provider adds the record the service produces
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Escrow {
+    pub id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    /// Stellar Asset Contract (or any Soroban token) the payment is made in
+    pub token: Address,
+    pub amount: i128,
+    /// The kind of record that settles the escrow
+    pub record_type: RecordType,
+    /// After this, the payment can only be refunded
+    pub deadline: u64,
+    pub status: EscrowStatus,
+    /// The record that matched the escrow, 0 until then
+    pub record_id: u64,
+    pub created_at: u64,
+}
+
+/// Internal store schema helpers
+pub fn escrow_key(escrow_id: u64) -> (Symbol, u64) {
+    (symbol_short!("ESCROW"), escrow_id)
+}
+
+/// Held escrows between a patient and a provider, oldest first
+pub fn held_key(patient: &Address, provider: &Address) -> (Symbol, Address, Address) {
+    (symbol_short!("ESC_HELD"), patient.clone(), provider.clone())
+}
+
+pub fn patient_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("PAT_ESC"), patient.clone())
+}
+
+pub fn provider_key(provider: &Address) -> (Symbol, Address) {
+    (symbol_short!("PRV_ESC"), provider.clone())
+}
+
+// ======================== Escrows ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_escrow_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&ESC_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&ESC_CTR, &id);
+    id
+}
+
+pub fn get_escrow(env: &Env, escrow_id: u64) -> Option<Escrow> {
+    env.storage().persistent().get(&escrow_key(escrow_id))
+}
+
+pub fn save_escrow(env: &Env, escrow: &Escrow) {
+    env.storage()
+        .persistent()
+        .set(&escrow_key(escrow.id), escrow);
+}
+
+pub fn get_held(env: &Env, patient: &Address, provider: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&held_key(patient, provider))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn get_escrows(env: &Env, key: (Symbol, Address)) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Move the payment into the contract and store the escrow
+pub fn open(env: &Env, escrow: &Escrow) {
+    token::Client::new(env, &escrow.token).transfer(
+        &escrow.patient,
+        &env.current_contract_address(),
+        &escrow.amount,
+    );
+    save_escrow(env, escrow);
+
+    let mut held = get_held(env, &escrow.patient, &escrow.provider);
+    held.push_back(escrow.id);
+    env.storage()
+        .persistent()
+        .set(&held_key(&escrow.patient, &escrow.provider), &held);
+
+    for key in [patient_key(&escrow.patient), provider_key(&escrow.provider)] {
+        let mut ids = get_escrows(env, key.clone());
+        ids.push_back(escrow.id);
+        env.storage().persistent().set(&key, &ids);
+    }
+}
+
+fn remove_held(env: &Env, escrow: &Escrow) {
+    let mut held = get_held(env, &escrow.patient, &escrow.provider);
+    if let Some(index) = held.first_index_of(escrow.id) {
+        held.remove(index);
+        env.storage()
+            .persistent()
+            .set(&held_key(&escrow.patient, &escrow.provider), &held);
+    }
+}
+
+/// Earmark a held escrow for the provider once `record_id` matches it. No
+/// tokens move, so a misbehaving token cannot block adding records.
+pub fn mark_matched(env: &Env, escrow: &mut Escrow, record_id: u64) {
+    escrow.status = EscrowStatus::Matched;
+    escrow.record_id = record_id;
+    save_escrow(env, escrow);
+    remove_held(env, escrow);
+}
+
+/// Pay out an escrow to `recipient` and mark it settled with `status`
+pub fn settle(env: &Env, escrow: &mut Escrow, recipient: &Address, status: EscrowStatus) {
+    token::Client::new(env, &escrow.token).transfer(
+        &env.current_contract_address(),
+        recipient,
+        &escrow.amount,
+    );
+    escrow.status = status;
+    save_escrow(env, escrow);
+    remove_held(env, escrow);
+}
+
+/// The oldest unexpired escrow a new record of `record_type` matches
+pub fn find_match(
+    env: &Env,
+    patient: &Address,
+    provider: &Address,
+    record_type: &RecordType,
+) -> Option<Escrow> {
+    get_held(env, patient, provider)
+        .iter()
+        .filter_map(|escrow_id| get_escrow(env, escrow_id))
+        .find(|escrow| {
+            escrow.record_type == *record_type && escrow.deadline > env.ledger().timestamp()
+        })
+}
@@ -1,10 +1,36 @@
-use crate::{AccessLevel, RecordType, Role};
-use soroban_sdk::{symbol_short, Address, Env, String};
+use crate::appointment::{Appointment, AppointmentStatus};
+use crate::attestation::{Attestation, ClaimType};
+use crate::care_team::CareTeam;
+use crate::claim::{Claim, ClaimStatus};
+use crate::consent::{ConsentDirective, PurposeOfUse};
+use crate::credentials::CredentialStatus;
+use crate::escrow::{Escrow, EscrowStatus};
+use crate::groups::{GrantTarget, GroupGrant};
+use crate::links::LinkType;
+use crate::organization::{OrgMember, OrgRole};
+use crate::prescription::{LensType, Prescription};
+use crate::rate_limit::{RateLimitScope, RateLimitedAction};
+use crate::rbac::CustomRole;
+use crate::referral::{Referral, ReferralStatus, ReferralUrgency};
+use crate::research::ResearchConsent;
+use crate::{AccessGrant, AccessLevel, Permission, RecordType, Role};
+use soroban_sdk::{symbol_short, Address, Env, String, Vec};
+
+/// Version of the event payload layout. Bumped whenever a field is added,
+/// removed or reinterpreted, so indexers can branch on it across upgrades.
+///
+/// Every payload starts with `schema_version` and `actor`. The actor is the
+/// address that authorized the call, except where an event says otherwise.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
 
 /// Event published when the contract is initialized.
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InitializedEvent {
+    pub schema_version: u32,
+    /// The new admin. `initialize` requires no authorization, so this is
+    /// not proof that the admin signed anything.
+    pub actor: Address,
     pub admin: Address,
     pub timestamp: u64,
 }
@@ -13,9 +39,143 @@ pub struct InitializedEvent {
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UserRegisteredEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub user: Address,
+    pub role: Role,
+    pub name: String,
+    pub timestamp: u64,
+}
+
+/// Event published when a role is assigned to a user, replacing any
+/// previous assignment and its custom grants and revocations.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleAssignedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub user: Address,
+    pub role: Role,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a custom permission is granted to or revoked from a user.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomPermissionEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub user: Address,
+    pub permission: Permission,
+    pub timestamp: u64,
+}
+
+/// Event published when a user delegates a role to another user.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleDelegatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub delegator: Address,
+    pub delegatee: Address,
+    pub role: Role,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a user is given an additional role, or when an
+/// additional role's expiry is replaced.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleAddedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub user: Address,
+    pub role: Role,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when an additional role is removed from a user.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleRemovedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub user: Address,
+    pub role: Role,
+    pub timestamp: u64,
+}
+
+/// Event published when an expired role assignment is removed.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleExpiredEvent {
+    pub schema_version: u32,
+    pub actor: Address,
     pub user: Address,
     pub role: Role,
+    pub expired_at: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when the permissions of a role are replaced.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RolePermissionsSetEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub role: Role,
+    pub permissions: Vec<Permission>,
+    pub timestamp: u64,
+}
+
+/// Event published when a custom role is defined or its permissions change.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomRoleSetEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub role_id: u64,
     pub name: String,
+    pub permissions: Vec<Permission>,
+    pub timestamp: u64,
+}
+
+/// Event published when a custom role is assigned to a user.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomRoleAssignedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub user: Address,
+    pub role_id: u64,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a user's custom role is removed.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomRoleRevokedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub user: Address,
+    pub role_id: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a user delegates their custom role.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomRoleDelegatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub delegator: Address,
+    pub delegatee: Address,
+    pub role_id: u64,
+    pub expires_at: u64,
     pub timestamp: u64,
 }
 
@@ -23,6 +183,8 @@ pub struct UserRegisteredEvent {
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RecordAddedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
     pub record_id: u64,
     pub patient: Address,
     pub provider: Address,
@@ -34,9 +196,12 @@ pub struct RecordAddedEvent {
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AccessGrantedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
     pub patient: Address,
     pub grantee: Address,
     pub level: AccessLevel,
+    pub purposes: Vec<PurposeOfUse>,
     pub duration_seconds: u64,
     pub expires_at: u64,
     pub timestamp: u64,
@@ -46,74 +211,1362 @@ pub struct AccessGrantedEvent {
 #[soroban_sdk::contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AccessRevokedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
     pub patient: Address,
     pub grantee: Address,
     pub timestamp: u64,
 }
 
-pub fn publish_initialized(env: &Env, admin: Address) {
-    let topics = (symbol_short!("INIT"),);
-    let data = InitializedEvent {
-        admin,
-        timestamp: env.ledger().timestamp(),
-    };
-    env.events().publish(topics, data);
+/// Event published when two records are linked.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordsLinkedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub source_id: u64,
+    pub target_id: u64,
+    pub link_type: LinkType,
+    pub timestamp: u64,
 }
 
-pub fn publish_user_registered(env: &Env, user: Address, role: Role, name: String) {
-    let topics = (symbol_short!("USR_REG"), user.clone());
-    let data = UserRegisteredEvent {
-        user,
-        role,
-        name,
-        timestamp: env.ledger().timestamp(),
-    };
-    env.events().publish(topics, data);
+/// Event published when an episode of care is opened.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpisodeCreatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub episode_id: u64,
+    pub patient: Address,
+    pub timestamp: u64,
 }
 
-pub fn publish_record_added(
-    env: &Env,
-    record_id: u64,
-    patient: Address,
-    provider: Address,
-    record_type: RecordType,
-) {
-    let topics = (symbol_short!("REC_ADD"), patient.clone(), provider.clone());
-    let data = RecordAddedEvent {
-        record_id,
-        patient,
-        provider,
-        record_type,
-        timestamp: env.ledger().timestamp(),
-    };
-    env.events().publish(topics, data);
+/// Event published when a record is added to an episode of care.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpisodeRecordAddedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub episode_id: u64,
+    pub record_id: u64,
+    pub timestamp: u64,
 }
 
-pub fn publish_access_granted(
-    env: &Env,
-    patient: Address,
-    grantee: Address,
-    level: AccessLevel,
-    duration_seconds: u64,
-    expires_at: u64,
-) {
-    let topics = (symbol_short!("ACC_GRT"), patient.clone(), grantee.clone());
-    let data = AccessGrantedEvent {
-        patient,
-        grantee,
-        level,
-        duration_seconds,
-        expires_at,
-        timestamp: env.ledger().timestamp(),
-    };
-    env.events().publish(topics, data);
+/// Event published when a structured prescription is issued.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrescriptionIssuedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub prescription_id: u64,
+    pub record_id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub lens_type: LensType,
+    pub expires_at: u64,
+    pub timestamp: u64,
 }
 
-pub fn publish_access_revoked(env: &Env, patient: Address, grantee: Address) {
-    let topics = (symbol_short!("ACC_REV"), patient.clone(), grantee.clone());
-    let data = AccessRevokedEvent {
-        patient,
-        grantee,
+/// Event published when structured examination results are recorded.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExaminationRecordedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub record_id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub timestamp: u64,
+}
+
+/// Event published when an optometrist refers a patient to a specialist.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralCreatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub referral_id: u64,
+    pub patient: Address,
+    pub referrer: Address,
+    pub specialist: Address,
+    pub urgency: ReferralUrgency,
+    pub access_expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a referral is accepted, declined or completed.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralUpdatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub referral_id: u64,
+    pub patient: Address,
+    pub specialist: Address,
+    pub status: ReferralStatus,
+    pub outcome_record_id: Option<u64>,
+    pub timestamp: u64,
+}
+
+/// Event published when a verifier attests a provider credential.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CredentialAttestedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub provider: Address,
+    pub verifier: Address,
+    pub specialty: Role,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a provider credential is suspended or reinstated.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CredentialStatusChangedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub provider: Address,
+    pub verifier: Address,
+    pub status: CredentialStatus,
+    pub timestamp: u64,
+}
+
+/// Event published when a record is read through `access_record`.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordAccessedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub record_id: u64,
+    pub patient: Address,
+    pub purpose: PurposeOfUse,
+    pub timestamp: u64,
+}
+
+/// Event published when a patient sets their consent directive.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentDirectiveSetEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub patient: Address,
+    pub default_purposes: Vec<PurposeOfUse>,
+    pub denied_purposes: Vec<PurposeOfUse>,
+    pub timestamp: u64,
+}
+
+/// Event published when a researcher registers a study.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StudyCreatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub study_id: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a patient opts in to a study, or to all studies
+/// (`study_id` 0). The actor is the contract, so the patient stays out of
+/// the payload.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResearchOptInEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub study_id: u64,
+    pub record_types: Vec<RecordType>,
+    pub timestamp: u64,
+}
+
+/// Event published when a patient withdraws a research opt-in. The actor is
+/// the contract, as for `ResearchOptInEvent`.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResearchOptOutEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub study_id: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when an organization is created.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrganizationCreatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub org_id: u64,
+    pub name: String,
+    pub timestamp: u64,
+}
+
+/// Event published when a member is added to an organization, or their role
+/// or duty status changes.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrgMemberSetEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub org_id: u64,
+    pub member: Address,
+    pub role: OrgRole,
+    pub on_duty: bool,
+    pub timestamp: u64,
+}
+
+/// Event published when a member leaves an organization.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrgMemberRemovedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub org_id: u64,
+    pub member: Address,
+    pub timestamp: u64,
+}
+
+/// Event published when a record is added on behalf of an organization.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrgRecordAddedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub org_id: u64,
+    pub record_id: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a patient creates a care team.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CareTeamCreatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub team_id: u64,
+    pub patient: Address,
+    pub name: String,
+    pub timestamp: u64,
+}
+
+/// Event published when a member is added to or removed from a care team.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CareTeamMemberEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub team_id: u64,
+    pub member: Address,
+    pub timestamp: u64,
+}
+
+/// Event published when a user registers or replaces their public encryption key.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncryptionKeySetEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub user: Address,
+    pub timestamp: u64,
+}
+
+/// Event published when key envelopes for a grantee are written, or marked
+/// stale after their access is revoked.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyEnvelopesEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub patient: Address,
+    pub grantee: Address,
+    pub record_ids: Vec<u64>,
+    pub timestamp: u64,
+}
+
+/// Event published when revocation advances a patient's key epoch.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyEpochAdvancedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub patient: Address,
+    pub epoch: u64,
+    /// Records now pending re-encryption
+    pub pending: Vec<u64>,
+    pub timestamp: u64,
+}
+
+/// Event published when a record is re-encrypted under the current epoch.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordKeyRotatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub patient: Address,
+    pub record_id: u64,
+    pub epoch: u64,
+    pub data_hash: String,
+    pub timestamp: u64,
+}
+
+/// Event published when a provider issues an attestation about a patient.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttestationIssuedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub attestation_id: u64,
+    pub subject: Address,
+    pub claim_type: ClaimType,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when an attestation is revoked.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttestationRevokedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub attestation_id: u64,
+    pub subject: Address,
+    pub timestamp: u64,
+}
+
+/// Event published when an appointment is booked.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppointmentBookedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub appointment_id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when an appointment is confirmed, cancelled or marked a no-show.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppointmentStatusEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub appointment_id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub status: AppointmentStatus,
+    pub timestamp: u64,
+}
+
+/// Event published when a provider submits an insurance claim.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimSubmittedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub claim_id: u64,
+    pub provider: Address,
+    pub insurer: Address,
+    pub record_count: u32,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Event published when an insurer approves or denies a claim.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimDecidedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub claim_id: u64,
+    pub provider: Address,
+    pub insurer: Address,
+    pub status: ClaimStatus,
+    pub timestamp: u64,
+}
+
+/// Event published when a patient escrows a payment.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowCreatedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub escrow_id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub record_type: RecordType,
+    pub deadline: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when an escrowed payment is released or refunded.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowSettledEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub escrow_id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub status: EscrowStatus,
+    pub record_id: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when an admin sets or removes a rate limit.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitSetEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub action: RateLimitedAction,
+    pub scope: RateLimitScope,
+    /// 0 when the limit was removed
+    pub max_calls: u32,
+    pub window_seconds: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a patient grants a group access.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupAccessGrantedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub patient: Address,
+    pub target: GrantTarget,
+    pub level: AccessLevel,
+    pub purposes: Vec<PurposeOfUse>,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+/// Event published when a patient revokes a group's access.
+#[soroban_sdk::contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupAccessRevokedEvent {
+    pub schema_version: u32,
+    pub actor: Address,
+    pub patient: Address,
+    pub target: GrantTarget,
+    pub timestamp: u64,
+}
+
+pub fn publish_initialized(env: &Env, actor: Address, admin: Address) {
+    let topics = (symbol_short!("INIT"),);
+    let data = InitializedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        admin,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_user_registered(env: &Env, actor: Address, user: Address, role: Role, name: String) {
+    let topics = (symbol_short!("USR_REG"), user.clone());
+    let data = UserRegisteredEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        role,
+        name,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_role_assigned(
+    env: &Env,
+    actor: Address,
+    user: Address,
+    role: Role,
+    expires_at: u64,
+) {
+    let topics = (symbol_short!("ROLE_ASN"), user.clone());
+    let data = RoleAssignedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        role,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_permission_granted(
+    env: &Env,
+    actor: Address,
+    user: Address,
+    permission: Permission,
+) {
+    let topics = (symbol_short!("PERM_GRT"), user.clone());
+    let data = CustomPermissionEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        permission,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_permission_revoked(
+    env: &Env,
+    actor: Address,
+    user: Address,
+    permission: Permission,
+) {
+    let topics = (symbol_short!("PERM_REV"), user.clone());
+    let data = CustomPermissionEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        permission,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_role_delegated(
+    env: &Env,
+    actor: Address,
+    delegator: Address,
+    delegatee: Address,
+    role: Role,
+    expires_at: u64,
+) {
+    let topics = (
+        symbol_short!("DELEGATE"),
+        delegator.clone(),
+        delegatee.clone(),
+    );
+    let data = RoleDelegatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        delegator,
+        delegatee,
+        role,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_role_added(env: &Env, actor: Address, user: Address, role: Role, expires_at: u64) {
+    let topics = (symbol_short!("ROLE_ADD"), user.clone());
+    let data = RoleAddedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        role,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_role_removed(env: &Env, actor: Address, user: Address, role: Role) {
+    let topics = (symbol_short!("ROLE_REM"), user.clone());
+    let data = RoleRemovedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        role,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_role_expired(env: &Env, actor: Address, user: Address, role: Role, expired_at: u64) {
+    let topics = (symbol_short!("ROLE_EXP"), user.clone());
+    let data = RoleExpiredEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        role,
+        expired_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_role_permissions_set(
+    env: &Env,
+    actor: Address,
+    role: Role,
+    permissions: Vec<Permission>,
+) {
+    let topics = (symbol_short!("ROLE_PRM"), role.clone());
+    let data = RolePermissionsSetEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        role,
+        permissions,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_custom_role_set(env: &Env, actor: Address, role: &CustomRole) {
+    let topics = (symbol_short!("CROLE_SET"), role.id);
+    let data = CustomRoleSetEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        role_id: role.id,
+        name: role.name.clone(),
+        permissions: role.permissions.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_custom_role_assigned(
+    env: &Env,
+    actor: Address,
+    user: Address,
+    role_id: u64,
+    expires_at: u64,
+) {
+    let topics = (symbol_short!("CROLE_ASN"), user.clone());
+    let data = CustomRoleAssignedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        role_id,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_custom_role_revoked(env: &Env, actor: Address, user: Address, role_id: u64) {
+    let topics = (symbol_short!("CROLE_REV"), user.clone());
+    let data = CustomRoleRevokedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        role_id,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_custom_role_delegated(
+    env: &Env,
+    actor: Address,
+    delegator: Address,
+    delegatee: Address,
+    role_id: u64,
+    expires_at: u64,
+) {
+    let topics = (
+        symbol_short!("CR_DELEG"),
+        delegator.clone(),
+        delegatee.clone(),
+    );
+    let data = CustomRoleDelegatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        delegator,
+        delegatee,
+        role_id,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_record_added(
+    env: &Env,
+    actor: Address,
+    record_id: u64,
+    patient: Address,
+    provider: Address,
+    record_type: RecordType,
+) {
+    let topics = (symbol_short!("REC_ADD"), patient.clone(), provider.clone());
+    let data = RecordAddedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        record_id,
+        patient,
+        provider,
+        record_type,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_access_granted(
+    env: &Env,
+    actor: Address,
+    grant: &AccessGrant,
+    purposes: Vec<PurposeOfUse>,
+    duration_seconds: u64,
+) {
+    let topics = (
+        symbol_short!("ACC_GRT"),
+        grant.patient.clone(),
+        grant.grantee.clone(),
+    );
+    let data = AccessGrantedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        patient: grant.patient.clone(),
+        grantee: grant.grantee.clone(),
+        level: grant.level.clone(),
+        purposes,
+        duration_seconds,
+        expires_at: grant.expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_access_revoked(env: &Env, actor: Address, patient: Address, grantee: Address) {
+    let topics = (symbol_short!("ACC_REV"), patient.clone(), grantee.clone());
+    let data = AccessRevokedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        patient,
+        grantee,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_records_linked(
+    env: &Env,
+    actor: Address,
+    source_id: u64,
+    target_id: u64,
+    link_type: LinkType,
+) {
+    let topics = (symbol_short!("REC_LINK"), source_id, target_id);
+    let data = RecordsLinkedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        source_id,
+        target_id,
+        link_type,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_episode_created(env: &Env, actor: Address, episode_id: u64, patient: Address) {
+    let topics = (symbol_short!("EPI_NEW"), patient.clone());
+    let data = EpisodeCreatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        episode_id,
+        patient,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_episode_record_added(env: &Env, actor: Address, episode_id: u64, record_id: u64) {
+    let topics = (symbol_short!("EPI_ADD"), episode_id);
+    let data = EpisodeRecordAddedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        episode_id,
+        record_id,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_prescription_issued(env: &Env, actor: Address, rx: &Prescription) {
+    let topics = (
+        symbol_short!("RX_ISSUE"),
+        rx.patient.clone(),
+        rx.provider.clone(),
+    );
+    let data = PrescriptionIssuedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        prescription_id: rx.id,
+        record_id: rx.record_id,
+        patient: rx.patient.clone(),
+        provider: rx.provider.clone(),
+        lens_type: rx.lens_type.clone(),
+        expires_at: rx.expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_examination_recorded(
+    env: &Env,
+    actor: Address,
+    record_id: u64,
+    patient: Address,
+    provider: Address,
+) {
+    let topics = (symbol_short!("EXAM_ADD"), patient.clone(), provider.clone());
+    let data = ExaminationRecordedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        record_id,
+        patient,
+        provider,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_referral_created(env: &Env, actor: Address, referral: &Referral) {
+    let topics = (
+        symbol_short!("REF_NEW"),
+        referral.patient.clone(),
+        referral.specialist.clone(),
+    );
+    let data = ReferralCreatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        referral_id: referral.id,
+        patient: referral.patient.clone(),
+        referrer: referral.referrer.clone(),
+        specialist: referral.specialist.clone(),
+        urgency: referral.urgency.clone(),
+        access_expires_at: referral.access_expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_referral_updated(
+    env: &Env,
+    actor: Address,
+    referral_id: u64,
+    patient: Address,
+    specialist: Address,
+    status: ReferralStatus,
+    outcome_record_id: Option<u64>,
+) {
+    let topics = (
+        symbol_short!("REF_UPD"),
+        patient.clone(),
+        specialist.clone(),
+    );
+    let data = ReferralUpdatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        referral_id,
+        patient,
+        specialist,
+        status,
+        outcome_record_id,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_credential_attested(
+    env: &Env,
+    actor: Address,
+    provider: Address,
+    verifier: Address,
+    specialty: Role,
+    expires_at: u64,
+) {
+    let topics = (
+        symbol_short!("CRED_SET"),
+        provider.clone(),
+        verifier.clone(),
+    );
+    let data = CredentialAttestedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        provider,
+        verifier,
+        specialty,
+        expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_credential_status_changed(
+    env: &Env,
+    actor: Address,
+    provider: Address,
+    verifier: Address,
+    status: CredentialStatus,
+) {
+    let topics = (
+        symbol_short!("CRED_STS"),
+        provider.clone(),
+        verifier.clone(),
+    );
+    let data = CredentialStatusChangedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        provider,
+        verifier,
+        status,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_record_accessed(
+    env: &Env,
+    actor: Address,
+    record_id: u64,
+    patient: Address,
+    purpose: PurposeOfUse,
+) {
+    let topics = (symbol_short!("REC_READ"), patient.clone(), actor.clone());
+    let data = RecordAccessedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        record_id,
+        patient,
+        purpose,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_consent_directive_set(env: &Env, actor: Address, directive: &ConsentDirective) {
+    let topics = (symbol_short!("CONSENT"), directive.patient.clone());
+    let data = ConsentDirectiveSetEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        patient: directive.patient.clone(),
+        default_purposes: directive.default_purposes.clone(),
+        denied_purposes: directive.denied_purposes.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_study_created(env: &Env, actor: Address, study_id: u64) {
+    let topics = (symbol_short!("STUDY_NEW"), study_id);
+    let data = StudyCreatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        study_id,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_research_opt_in(env: &Env, consent: &ResearchConsent) {
+    // Naming the patient anywhere in the event would tie them to the study
+    let topics = (symbol_short!("RS_OPTIN"), consent.study_id);
+    let data = ResearchOptInEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor: env.current_contract_address(),
+        study_id: consent.study_id,
+        record_types: consent.record_types.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_research_opt_out(env: &Env, study_id: u64) {
+    let topics = (symbol_short!("RS_OPTOUT"), study_id);
+    let data = ResearchOptOutEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor: env.current_contract_address(),
+        study_id,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_organization_created(env: &Env, actor: Address, org_id: u64, name: String) {
+    let topics = (symbol_short!("ORG_NEW"), org_id);
+    let data = OrganizationCreatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        org_id,
+        name,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_org_member_set(env: &Env, actor: Address, member: &OrgMember) {
+    let topics = (
+        symbol_short!("ORG_MEM"),
+        member.org_id,
+        member.member.clone(),
+    );
+    let data = OrgMemberSetEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        org_id: member.org_id,
+        member: member.member.clone(),
+        role: member.role.clone(),
+        on_duty: member.on_duty,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_org_member_removed(env: &Env, actor: Address, org_id: u64, member: Address) {
+    let topics = (symbol_short!("ORG_REM"), org_id, member.clone());
+    let data = OrgMemberRemovedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        org_id,
+        member,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_org_record_added(env: &Env, actor: Address, org_id: u64, record_id: u64) {
+    let topics = (symbol_short!("ORG_REC"), org_id, record_id);
+    let data = OrgRecordAddedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        org_id,
+        record_id,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_care_team_created(env: &Env, actor: Address, team: &CareTeam) {
+    let topics = (symbol_short!("CT_NEW"), team.patient.clone(), team.id);
+    let data = CareTeamCreatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        team_id: team.id,
+        patient: team.patient.clone(),
+        name: team.name.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_care_team_member_added(env: &Env, actor: Address, team_id: u64, member: Address) {
+    let topics = (symbol_short!("CT_ADD"), team_id, member.clone());
+    let data = CareTeamMemberEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        team_id,
+        member,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_care_team_member_removed(env: &Env, actor: Address, team_id: u64, member: Address) {
+    let topics = (symbol_short!("CT_REM"), team_id, member.clone());
+    let data = CareTeamMemberEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        team_id,
+        member,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_encryption_key_set(env: &Env, actor: Address, user: Address) {
+    let topics = (symbol_short!("KEY_SET"), user.clone());
+    let data = EncryptionKeySetEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        user,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_key_envelopes_set(
+    env: &Env,
+    actor: Address,
+    patient: Address,
+    grantee: Address,
+    record_ids: Vec<u64>,
+) {
+    let topics = (symbol_short!("ENV_SET"), patient.clone(), grantee.clone());
+    let data = KeyEnvelopesEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        patient,
+        grantee,
+        record_ids,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_key_envelopes_stale(
+    env: &Env,
+    actor: Address,
+    patient: Address,
+    grantee: Address,
+    record_ids: Vec<u64>,
+) {
+    let topics = (symbol_short!("ENV_STALE"), patient.clone(), grantee.clone());
+    let data = KeyEnvelopesEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        patient,
+        grantee,
+        record_ids,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_key_epoch_advanced(
+    env: &Env,
+    actor: Address,
+    patient: Address,
+    epoch: u64,
+    pending: Vec<u64>,
+) {
+    let topics = (symbol_short!("KEY_EPOCH"), patient.clone());
+    let data = KeyEpochAdvancedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        patient,
+        epoch,
+        pending,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_record_key_rotated(
+    env: &Env,
+    actor: Address,
+    patient: Address,
+    record_id: u64,
+    epoch: u64,
+    data_hash: String,
+) {
+    let topics = (symbol_short!("KEY_ROT"), patient.clone(), record_id);
+    let data = RecordKeyRotatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        patient,
+        record_id,
+        epoch,
+        data_hash,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_attestation_issued(env: &Env, actor: Address, attestation: &Attestation) {
+    let topics = (
+        symbol_short!("ATT_ISS"),
+        attestation.subject.clone(),
+        attestation.id,
+    );
+    let data = AttestationIssuedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        attestation_id: attestation.id,
+        subject: attestation.subject.clone(),
+        claim_type: attestation.claim_type.clone(),
+        expires_at: attestation.expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_attestation_revoked(
+    env: &Env,
+    actor: Address,
+    attestation_id: u64,
+    subject: Address,
+) {
+    let topics = (symbol_short!("ATT_REV"), subject.clone(), attestation_id);
+    let data = AttestationRevokedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        attestation_id,
+        subject,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_appointment_booked(env: &Env, actor: Address, appointment: &Appointment) {
+    let topics = (
+        symbol_short!("APPT_BOOK"),
+        appointment.patient.clone(),
+        appointment.provider.clone(),
+    );
+    let data = AppointmentBookedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        appointment_id: appointment.id,
+        patient: appointment.patient.clone(),
+        provider: appointment.provider.clone(),
+        start_time: appointment.start_time,
+        end_time: appointment.end_time,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_appointment_status_changed(env: &Env, actor: Address, appointment: &Appointment) {
+    let topics = (
+        symbol_short!("APPT_STAT"),
+        appointment.patient.clone(),
+        appointment.provider.clone(),
+    );
+    let data = AppointmentStatusEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        appointment_id: appointment.id,
+        patient: appointment.patient.clone(),
+        provider: appointment.provider.clone(),
+        status: appointment.status.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_claim_submitted(env: &Env, actor: Address, claim: &Claim) {
+    let topics = (
+        symbol_short!("CLM_SUB"),
+        claim.provider.clone(),
+        claim.insurer.clone(),
+    );
+    let data = ClaimSubmittedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        claim_id: claim.id,
+        provider: claim.provider.clone(),
+        insurer: claim.insurer.clone(),
+        record_count: claim.lines.len(),
+        amount: claim.amount,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_claim_decided(env: &Env, actor: Address, claim: &Claim) {
+    let topics = (
+        symbol_short!("CLM_STAT"),
+        claim.provider.clone(),
+        claim.insurer.clone(),
+    );
+    let data = ClaimDecidedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        claim_id: claim.id,
+        provider: claim.provider.clone(),
+        insurer: claim.insurer.clone(),
+        status: claim.status.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_escrow_created(env: &Env, actor: Address, escrow: &Escrow) {
+    let topics = (
+        symbol_short!("ESC_NEW"),
+        escrow.patient.clone(),
+        escrow.provider.clone(),
+    );
+    let data = EscrowCreatedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        escrow_id: escrow.id,
+        patient: escrow.patient.clone(),
+        provider: escrow.provider.clone(),
+        token: escrow.token.clone(),
+        amount: escrow.amount,
+        record_type: escrow.record_type.clone(),
+        deadline: escrow.deadline,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_escrow_settled(env: &Env, actor: Address, escrow: &Escrow) {
+    let topics = (
+        symbol_short!("ESC_STAT"),
+        escrow.patient.clone(),
+        escrow.provider.clone(),
+    );
+    let data = EscrowSettledEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        escrow_id: escrow.id,
+        patient: escrow.patient.clone(),
+        provider: escrow.provider.clone(),
+        status: escrow.status.clone(),
+        record_id: escrow.record_id,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_rate_limit_set(
+    env: &Env,
+    actor: Address,
+    action: RateLimitedAction,
+    scope: RateLimitScope,
+    max_calls: u32,
+    window_seconds: u64,
+) {
+    let topics = (symbol_short!("RL_SET"), action.clone());
+    let data = RateLimitSetEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        action,
+        scope,
+        max_calls,
+        window_seconds,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_group_access_granted(env: &Env, actor: Address, grant: &GroupGrant) {
+    let topics = (
+        symbol_short!("GRP_GRT"),
+        grant.patient.clone(),
+        grant.target.clone(),
+    );
+    let data = GroupAccessGrantedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        patient: grant.patient.clone(),
+        target: grant.target.clone(),
+        level: grant.level.clone(),
+        purposes: grant.purposes.clone(),
+        expires_at: grant.expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn publish_group_access_revoked(
+    env: &Env,
+    actor: Address,
+    patient: Address,
+    target: GrantTarget,
+) {
+    let topics = (symbol_short!("GRP_REV"), patient.clone(), target.clone());
+    let data = GroupAccessRevokedEvent {
+        schema_version: EVENT_SCHEMA_VERSION,
+        actor,
+        patient,
+        target,
         timestamp: env.ledger().timestamp(),
     };
     env.events().publish(topics, data);
@@ -0,0 +1,171 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+// ── Types ─────────────────────────────────────────────────────
+//
+// Measurements are stored as fixed-point integers:
+// - logMAR acuity in thousandths (0.300 -> 300)
+// - intraocular pressure in tenths of mmHg (15.5 mmHg -> 155)
+// - visual field indices in hundredths of a dB / percent
+
+/// Visual acuity in either Snellen or logMAR notation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VisualAcuity {
+    /// Test distance over letter size, e.g. 20/40 -> Snellen(20, 40)
+    Snellen(u32, u32),
+    LogMar(i32),
+}
+
+/// Global indices from a standard automated perimetry test
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VisualFieldIndices {
+    pub mean_deviation: i32,
+    pub pattern_std_deviation: u32,
+    pub visual_field_index: u32,
+}
+
+/// Visual field result; perimetry is not part of every exam
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VisualField {
+    NotPerformed,
+    Performed(VisualFieldIndices),
+}
+
+/// Findings for a single eye
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EyeExamination {
+    pub acuity: VisualAcuity,
+    /// Zero when not measured
+    pub intraocular_pressure: u32,
+    pub visual_field: VisualField,
+}
+
+/// Structured results attached to a `RecordType::Examination` record
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ExaminationResult {
+    pub record_id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub left_eye: EyeExamination,
+    pub right_eye: EyeExamination,
+    /// Off-chain references (e.g. IPFS hashes) to retinal imaging
+    pub imaging_refs: Vec<String>,
+    pub recorded_at: u64,
+}
+
+/// Measurement tracked across a patient's exam history
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExamMetric {
+    IntraocularPressure,
+    MeanDeviation,
+}
+
+/// One point of a per-eye measurement series
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrendPoint {
+    pub record_id: u64,
+    /// None when the eye was not measured at this exam
+    pub left: Option<i32>,
+    pub right: Option<i32>,
+    pub recorded_at: u64,
+}
+
+/// Upper bound for a plausible IOP reading (80.0 mmHg)
+pub const MAX_IOP: u32 = 800;
+
+/// Internal store schema helpers
+pub fn examination_key(record_id: u64) -> (Symbol, u64) {
+    (symbol_short!("EXAM"), record_id)
+}
+
+pub fn patient_examinations_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("PAT_EXAM"), patient.clone())
+}
+
+// ======================== Validation ========================
+
+pub fn is_valid_eye_examination(eye: &EyeExamination) -> bool {
+    if let VisualAcuity::Snellen(distance, size) = eye.acuity {
+        if distance == 0 || size == 0 {
+            return false;
+        }
+    }
+
+    eye.intraocular_pressure <= MAX_IOP
+}
+
+// ======================== Storage ========================
+
+pub fn save_examination(env: &Env, exam: &ExaminationResult) {
+    env.storage()
+        .persistent()
+        .set(&examination_key(exam.record_id), exam);
+
+    let mut patient_exams: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&patient_examinations_key(&exam.patient))
+        .unwrap_or(Vec::new(env));
+    patient_exams.push_back(exam.record_id);
+    env.storage()
+        .persistent()
+        .set(&patient_examinations_key(&exam.patient), &patient_exams);
+}
+
+pub fn get_examination(env: &Env, record_id: u64) -> Option<ExaminationResult> {
+    env.storage().persistent().get(&examination_key(record_id))
+}
+
+pub fn get_patient_examinations(env: &Env, patient: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&patient_examinations_key(patient))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Extract `metric` from a single exam, or None if it was not measured
+fn metric_value(eye: &EyeExamination, metric: &ExamMetric) -> Option<i32> {
+    match metric {
+        ExamMetric::IntraocularPressure => {
+            if eye.intraocular_pressure == 0 {
+                None
+            } else {
+                Some(eye.intraocular_pressure as i32)
+            }
+        }
+        ExamMetric::MeanDeviation => match &eye.visual_field {
+            VisualField::Performed(indices) => Some(indices.mean_deviation),
+            VisualField::NotPerformed => None,
+        },
+    }
+}
+
+/// Build the series of `metric` over a patient's examinations, oldest first.
+/// Exams measuring only one eye keep a point with the other side empty;
+/// exams missing the metric for both eyes are skipped.
+pub fn get_trend(env: &Env, patient: &Address, metric: &ExamMetric) -> Vec<TrendPoint> {
+    let mut points = Vec::new(env);
+
+    for record_id in get_patient_examinations(env, patient).iter() {
+        if let Some(exam) = get_examination(env, record_id) {
+            let left = metric_value(&exam.left_eye, metric);
+            let right = metric_value(&exam.right_eye, metric);
+            if left.is_some() || right.is_some() {
+                points.push_back(TrendPoint {
+                    record_id,
+                    left,
+                    right,
+                    recorded_at: exam.recorded_at,
+                });
+            }
+        }
+    }
+
+    points
+}
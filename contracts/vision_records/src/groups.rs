@@ -0,0 +1,132 @@
+use crate::care_team;
+use crate::consent::PurposeOfUse;
+use crate::organization;
+use crate::AccessLevel;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// A group that can hold an access grant in place of a single address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GrantTarget {
+    /// An organization; its on-duty providers are its members for access
+    Organization(u64),
+    /// A patient-curated care team; every listed member belongs
+    CareTeam(u64),
+}
+
+/// An access grant whose grantee is a group. Membership is resolved each
+/// time the grant is checked, so it never needs rewriting when the group
+/// changes.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct GroupGrant {
+    pub patient: Address,
+    pub target: GrantTarget,
+    pub level: AccessLevel,
+    pub purposes: Vec<PurposeOfUse>,
+    pub granted_at: u64,
+    pub expires_at: u64,
+}
+
+/// Internal store schema helpers
+pub fn grant_key(patient: &Address, target: &GrantTarget) -> (Symbol, Address, GrantTarget) {
+    (symbol_short!("GRP_ACC"), patient.clone(), target.clone())
+}
+
+/// Groups a patient has granted access to
+pub fn targets_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("GRP_TGTS"), patient.clone())
+}
+
+// ======================== Membership ========================
+
+/// Whether the target group exists
+pub fn exists(env: &Env, target: &GrantTarget) -> bool {
+    match target {
+        GrantTarget::Organization(org_id) => organization::get_org(env, *org_id).is_some(),
+        GrantTarget::CareTeam(team_id) => care_team::get_team(env, *team_id).is_some(),
+    }
+}
+
+/// Whether `user` currently counts as a member of the target group
+pub fn is_member(env: &Env, target: &GrantTarget, user: &Address) -> bool {
+    match target {
+        GrantTarget::Organization(org_id) => organization::is_on_duty_provider(env, *org_id, user),
+        GrantTarget::CareTeam(team_id) => care_team::is_member(env, *team_id, user),
+    }
+}
+
+/// Everyone a grant to the target could have covered: every listed member
+/// of an organization (duty status changes over time) or of a care team
+pub fn members(env: &Env, target: &GrantTarget) -> Vec<Address> {
+    match target {
+        GrantTarget::Organization(org_id) => organization::get_members(env, *org_id),
+        GrantTarget::CareTeam(team_id) => care_team::get_team(env, *team_id)
+            .map(|team| team.members)
+            .unwrap_or(Vec::new(env)),
+    }
+}
+
+// ======================== Grants ========================
+
+pub fn get_targets(env: &Env, patient: &Address) -> Vec<GrantTarget> {
+    env.storage()
+        .persistent()
+        .get(&targets_key(patient))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_grant(env: &Env, grant: &GroupGrant) {
+    env.storage()
+        .persistent()
+        .set(&grant_key(&grant.patient, &grant.target), grant);
+
+    let mut targets = get_targets(env, &grant.patient);
+    if !targets.contains(&grant.target) {
+        targets.push_back(grant.target.clone());
+        env.storage()
+            .persistent()
+            .set(&targets_key(&grant.patient), &targets);
+    }
+}
+
+/// The patient's unexpired grant to the target group, if any
+pub fn get_active_grant(env: &Env, patient: &Address, target: &GrantTarget) -> Option<GroupGrant> {
+    env.storage()
+        .persistent()
+        .get::<_, GroupGrant>(&grant_key(patient, target))
+        .filter(|grant| grant.expires_at > env.ledger().timestamp())
+}
+
+/// The patient's unexpired group grants that `user` is currently covered by
+pub fn get_member_grants(env: &Env, patient: &Address, user: &Address) -> Vec<GroupGrant> {
+    let mut grants = Vec::new(env);
+    for target in get_targets(env, patient).iter() {
+        if let Some(grant) = get_active_grant(env, patient, &target) {
+            if is_member(env, &target, user) {
+                grants.push_back(grant);
+            }
+        }
+    }
+    grants
+}
+
+/// Remove a grant. Returns false if there was none.
+pub fn remove_grant(env: &Env, patient: &Address, target: &GrantTarget) -> bool {
+    let key = grant_key(patient, target);
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+    env.storage().persistent().remove(&key);
+
+    let mut targets = get_targets(env, patient);
+    if let Some(index) = targets.first_index_of(target) {
+        targets.remove(index);
+        env.storage()
+            .persistent()
+            .set(&targets_key(patient), &targets);
+    }
+    true
+}
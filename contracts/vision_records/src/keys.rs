@@ -0,0 +1,174 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, Env, Symbol, Vec};
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// A record's content key, encrypted to one grantee's public key. The
+/// contract never sees the plaintext key and treats the envelope as opaque.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct KeyEnvelope {
+    pub record_id: u64,
+    pub grantee: Address,
+    pub wrapped_key: Bytes,
+    /// The record's key epoch the envelope was issued under; envelopes from
+    /// before a rotation wrap a retired key
+    pub epoch: u64,
+    pub created_at: u64,
+    /// Set when the grantee's access is revoked; stale envelopes are not served
+    pub stale: bool,
+}
+
+/// Internal store schema helpers
+pub fn public_key_key(user: &Address) -> (Symbol, Address) {
+    (symbol_short!("ENC_KEY"), user.clone())
+}
+
+pub fn envelope_key(record_id: u64, grantee: &Address) -> (Symbol, u64, Address) {
+    (symbol_short!("ENVELOPE"), record_id, grantee.clone())
+}
+
+/// Records of `patient` holding an envelope for `grantee`
+pub fn envelope_records_key(patient: &Address, grantee: &Address) -> (Symbol, Address, Address) {
+    (symbol_short!("ENV_RECS"), patient.clone(), grantee.clone())
+}
+
+pub fn key_epoch_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("KEY_EPOCH"), patient.clone())
+}
+
+pub fn record_epoch_key(record_id: u64) -> (Symbol, u64) {
+    (symbol_short!("REC_EPOCH"), record_id)
+}
+
+/// Records of `patient` still encrypted under a retired key
+pub fn pending_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("ROT_PEND"), patient.clone())
+}
+
+// ======================== Public Keys ========================
+
+pub fn set_public_key(env: &Env, user: &Address, public_key: &Bytes) {
+    env.storage()
+        .persistent()
+        .set(&public_key_key(user), public_key);
+}
+
+pub fn get_public_key(env: &Env, user: &Address) -> Option<Bytes> {
+    env.storage().persistent().get(&public_key_key(user))
+}
+
+// ======================== Envelopes ========================
+
+pub fn get_envelope(env: &Env, record_id: u64, grantee: &Address) -> Option<KeyEnvelope> {
+    env.storage()
+        .persistent()
+        .get(&envelope_key(record_id, grantee))
+}
+
+pub fn get_envelope_records(env: &Env, patient: &Address, grantee: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&envelope_records_key(patient, grantee))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Store a fresh envelope for a record of `patient`, replacing any earlier one
+pub fn set_envelope(env: &Env, patient: &Address, envelope: &KeyEnvelope) {
+    env.storage().persistent().set(
+        &envelope_key(envelope.record_id, &envelope.grantee),
+        envelope,
+    );
+
+    let mut records = get_envelope_records(env, patient, &envelope.grantee);
+    if !records.contains(envelope.record_id) {
+        records.push_back(envelope.record_id);
+        env.storage()
+            .persistent()
+            .set(&envelope_records_key(patient, &envelope.grantee), &records);
+    }
+}
+
+/// Mark every envelope `grantee` holds for `patient`'s records as stale.
+/// Returns the affected record IDs.
+pub fn mark_stale(env: &Env, patient: &Address, grantee: &Address) -> Vec<u64> {
+    let mut marked = Vec::new(env);
+    for record_id in get_envelope_records(env, patient, grantee).iter() {
+        if let Some(mut envelope) = get_envelope(env, record_id, grantee) {
+            if !envelope.stale {
+                envelope.stale = true;
+                env.storage()
+                    .persistent()
+                    .set(&envelope_key(record_id, grantee), &envelope);
+                marked.push_back(record_id);
+            }
+        }
+    }
+    marked
+}
+
+// ======================== Rotation ========================
+
+/// The patient's current key epoch, starting at 0
+pub fn get_key_epoch(env: &Env, patient: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&key_epoch_key(patient))
+        .unwrap_or(0)
+}
+
+/// Advance the patient's key epoch and queue `record_ids` for re-encryption.
+/// Returns the new epoch.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn bump_key_epoch(env: &Env, patient: &Address, record_ids: &Vec<u64>) -> u64 {
+    let epoch = get_key_epoch(env, patient) + 1;
+    env.storage()
+        .persistent()
+        .set(&key_epoch_key(patient), &epoch);
+
+    let mut pending = get_pending(env, patient);
+    for record_id in record_ids.iter() {
+        if !pending.contains(record_id) {
+            pending.push_back(record_id);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&pending_key(patient), &pending);
+    epoch
+}
+
+/// The key epoch a record's current payload is encrypted under
+pub fn get_record_epoch(env: &Env, record_id: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&record_epoch_key(record_id))
+        .unwrap_or(0)
+}
+
+pub fn set_record_epoch(env: &Env, record_id: u64, epoch: u64) {
+    env.storage()
+        .persistent()
+        .set(&record_epoch_key(record_id), &epoch);
+}
+
+pub fn get_pending(env: &Env, patient: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&pending_key(patient))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Take a record off the pending list. Returns false if it was not on it.
+pub fn clear_pending(env: &Env, patient: &Address, record_id: u64) -> bool {
+    let mut pending = get_pending(env, patient);
+    match pending.first_index_of(record_id) {
+        Some(index) => {
+            pending.remove(index);
+            env.storage()
+                .persistent()
+                .set(&pending_key(patient), &pending);
+            true
+        }
+        None => false,
+    }
+}
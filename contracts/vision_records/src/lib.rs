@@ -1,17 +1,57 @@
 #![no_std]
+pub mod appointment;
+pub mod attestation;
+pub mod audit;
+pub mod care_team;
+pub mod claim;
+pub mod consent;
+pub mod credentials;
+pub mod escrow;
 pub mod rbac;
+pub mod referral;
+pub mod research;
 
 pub mod events;
+pub mod examination;
+pub mod groups;
+pub mod keys;
+pub mod links;
+pub mod merkle;
+pub mod organization;
+pub mod prescription;
+pub mod rate_limit;
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, String, Symbol, Vec,
+    contract, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env, Map, String,
+    Symbol, Vec,
 };
 
 /// Storage keys for the contract
 const ADMIN: Symbol = symbol_short!("ADMIN");
 const INITIALIZED: Symbol = symbol_short!("INIT");
 
-pub use rbac::{Permission, Role};
+pub use appointment::{Appointment, AppointmentStatus};
+pub use attestation::{Attestation, ClaimType};
+pub use audit::ReadAuditEntry;
+pub use care_team::CareTeam;
+pub use claim::{Claim, ClaimLine, ClaimStatus};
+pub use consent::{ConsentDirective, PurposeOfUse};
+pub use credentials::{CredentialStatus, ProviderCredential};
+pub use escrow::{Escrow, EscrowStatus};
+pub use examination::{
+    ExamMetric, ExaminationResult, EyeExamination, TrendPoint, VisualAcuity, VisualField,
+    VisualFieldIndices,
+};
+pub use groups::{GrantTarget, GroupGrant};
+pub use keys::KeyEnvelope;
+pub use links::{Episode, LinkType, LinkedGraph, RecordLink};
+pub use merkle::{MerkleProof, RecordLeaf};
+pub use organization::{OrgMember, OrgRole, Organization};
+pub use prescription::{EyeRx, LensType, Prescription, PrismBase};
+pub use rate_limit::{RateLimit, RateLimitScope, RateLimitedAction};
+pub use rbac::{CustomRole, Permission, Role};
+pub use referral::{RecordGrant, Referral, ReferralStatus, ReferralUrgency};
+pub use research::{CohortMember, CohortRecord, ResearchConsent, Study};
 
 /// Access levels for record sharing
 #[contracttype]
@@ -59,6 +99,18 @@ pub struct VisionRecord {
     pub updated_at: u64,
 }
 
+/// A record's metadata, without the data hash
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RecordMetadata {
+    pub id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub record_type: RecordType,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
 /// Access grant structure
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -84,6 +136,487 @@ pub enum ContractError {
     InvalidInput = 6,
     AccessDenied = 7,
     Paused = 8,
+    EpisodeNotFound = 9,
+    PrescriptionNotFound = 10,
+    ExaminationNotFound = 11,
+    ReferralNotFound = 12,
+    CredentialNotFound = 13,
+    StudyNotFound = 14,
+    CustomRoleNotFound = 15,
+    OrganizationNotFound = 16,
+    CareTeamNotFound = 17,
+    KeyNotFound = 18,
+    AttestationNotFound = 19,
+    AppointmentNotFound = 20,
+    ClaimNotFound = 21,
+    EscrowNotFound = 22,
+    RateLimited = 23,
+}
+
+/// Whether `caller` may write records as `provider`, either directly,
+/// through a delegated role, or as a system admin.
+fn can_write_for_provider(env: &Env, caller: &Address, provider: &Address) -> bool {
+    let has_perm = if caller == provider {
+        rbac::has_permission(env, caller, &Permission::WriteRecord)
+    } else {
+        rbac::has_delegated_permission(env, provider, caller, &Permission::WriteRecord)
+    };
+
+    has_perm || rbac::has_permission(env, caller, &Permission::SystemAdmin)
+}
+
+/// When a patient-wide grant starts to apply, kept apart from the
+/// `AccessGrant` so existing grants still decode
+fn grant_not_before_key(patient: &Address, grantee: &Address) -> (Symbol, Address, Address) {
+    (symbol_short!("GRT_NBF"), patient.clone(), grantee.clone())
+}
+
+/// The time before which `grantee`'s grant does not apply, or 0 if it
+/// applies from when it was made
+fn get_grant_not_before(env: &Env, patient: &Address, grantee: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&grant_not_before_key(patient, grantee))
+        .unwrap_or(0)
+}
+
+fn set_grant_not_before(env: &Env, patient: &Address, grantee: &Address, not_before: u64) {
+    let key = grant_not_before_key(patient, grantee);
+    if not_before == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &not_before);
+    }
+}
+
+/// The unexpired patient-wide grant held by `grantee`, if any, whether or
+/// not it has started
+fn get_unexpired_grant(env: &Env, patient: &Address, grantee: &Address) -> Option<AccessGrant> {
+    let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
+    env.storage()
+        .persistent()
+        .get::<_, AccessGrant>(&key)
+        .filter(|grant| grant.expires_at > env.ledger().timestamp())
+}
+
+/// The patient-wide grant held by `grantee` that has started and not yet
+/// expired, if any
+fn get_active_grant(env: &Env, patient: &Address, grantee: &Address) -> Option<AccessGrant> {
+    get_unexpired_grant(env, patient, grantee)
+        .filter(|_| get_grant_not_before(env, patient, grantee) <= env.ledger().timestamp())
+}
+
+/// Order of access levels, lowest first
+pub(crate) fn access_rank(level: &AccessLevel) -> u32 {
+    match level {
+        AccessLevel::None => 0,
+        AccessLevel::Read => 1,
+        AccessLevel::Write => 2,
+        AccessLevel::Full => 3,
+    }
+}
+
+/// Whether `caller` may manage `patient`'s grants: the patient, a delegate
+/// holding `ManageAccess`, or a system admin
+fn can_manage_access(env: &Env, caller: &Address, patient: &Address) -> bool {
+    *caller == *patient
+        || rbac::has_delegated_permission(env, patient, caller, &Permission::ManageAccess)
+        || rbac::has_permission(env, caller, &Permission::SystemAdmin)
+}
+
+/// Store a patient-wide grant after checking the caller's authority and the
+/// patient's consent directive. Grants without explicit purposes take the
+/// directive's defaults.
+fn grant_patient_access(
+    env: &Env,
+    caller: Address,
+    patient: Address,
+    grantee: Address,
+    level: AccessLevel,
+    duration_seconds: u64,
+    purposes: Option<Vec<PurposeOfUse>>,
+) -> Result<(), ContractError> {
+    caller.require_auth();
+
+    if !can_manage_access(env, &caller, &patient) {
+        return Err(ContractError::Unauthorized);
+    }
+    check_rate_limits(env, RateLimitedAction::GrantAccess, &caller, &patient)?;
+
+    store_patient_grant(
+        env,
+        caller,
+        patient,
+        grantee,
+        level,
+        duration_seconds,
+        purposes,
+    )
+}
+
+/// Store a patient-wide grant after checking it against the patient's consent
+/// directive. Callers check the caller's authority first.
+#[allow(clippy::arithmetic_side_effects)]
+fn store_patient_grant(
+    env: &Env,
+    caller: Address,
+    patient: Address,
+    grantee: Address,
+    level: AccessLevel,
+    duration_seconds: u64,
+    purposes: Option<Vec<PurposeOfUse>>,
+) -> Result<(), ContractError> {
+    let directive = consent::get_directive(env, &patient);
+    let purposes = match purposes {
+        Some(purposes) => {
+            if purposes.is_empty() {
+                return Err(ContractError::InvalidInput);
+            }
+            if !consent::permits_all(&directive, &purposes) {
+                return Err(ContractError::AccessDenied);
+            }
+            purposes
+        }
+        None => directive.default_purposes,
+    };
+
+    let expires_at = env.ledger().timestamp() + duration_seconds;
+    let grant = AccessGrant {
+        patient: patient.clone(),
+        grantee: grantee.clone(),
+        level,
+        granted_at: env.ledger().timestamp(),
+        expires_at,
+    };
+
+    let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
+    env.storage().persistent().set(&key, &grant);
+    consent::set_grant_purposes(env, &patient, &grantee, &purposes);
+    set_grant_not_before(env, &patient, &grantee, 0);
+
+    events::publish_access_granted(env, caller, &grant, purposes, duration_seconds);
+
+    Ok(())
+}
+
+/// Remove a patient-wide grant. Envelopes the grantee held go stale, and if
+/// the grant ever took effect, or the grantee held fresh envelopes, the
+/// patient's key epoch advances.
+fn revoke_patient_access(env: &Env, actor: Address, patient: Address, grantee: Address) {
+    let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
+    let had_grant = env.storage().persistent().has(&key);
+    let started = get_grant_not_before(env, &patient, &grantee) <= env.ledger().timestamp();
+    env.storage().persistent().remove(&key);
+    consent::remove_grant_purposes(env, &patient, &grantee);
+    set_grant_not_before(env, &patient, &grantee, 0);
+
+    events::publish_access_revoked(env, actor.clone(), patient.clone(), grantee.clone());
+
+    let held_keys = mark_envelopes_stale(env, actor.clone(), &patient, grantee);
+
+    // The revoked party may have kept content keys, so every record is
+    // re-encrypted under a new epoch. A grant that never started, to someone
+    // without envelopes, gave them nothing to keep.
+    if had_grant && (started || held_keys) {
+        advance_key_epoch(env, actor, &patient);
+    }
+}
+
+/// Mark the envelopes `grantee` holds for `patient`'s records stale and
+/// announce the ones that were still fresh. Returns whether there were any.
+fn mark_envelopes_stale(env: &Env, actor: Address, patient: &Address, grantee: Address) -> bool {
+    let stale = keys::mark_stale(env, patient, &grantee);
+    if stale.is_empty() {
+        return false;
+    }
+    events::publish_key_envelopes_stale(env, actor, patient.clone(), grantee, stale);
+    true
+}
+
+/// Queue every record of `patient` for re-encryption under a new key epoch
+fn advance_key_epoch(env: &Env, actor: Address, patient: &Address) {
+    let records = VisionRecordsContract::get_patient_records(env.clone(), patient.clone());
+    let epoch = keys::bump_key_epoch(env, patient, &records);
+    events::publish_key_epoch_advanced(
+        env,
+        actor,
+        patient.clone(),
+        epoch,
+        keys::get_pending(env, patient),
+    );
+}
+
+/// Overwrite the window of `grantee`'s existing grant, keeping its level and
+/// purposes
+fn set_grant_window(
+    env: &Env,
+    actor: Address,
+    mut grant: AccessGrant,
+    not_before: u64,
+    expires_at: u64,
+) {
+    grant.expires_at = expires_at;
+    let key = (
+        symbol_short!("ACCESS"),
+        grant.patient.clone(),
+        grant.grantee.clone(),
+    );
+    env.storage().persistent().set(&key, &grant);
+    set_grant_not_before(env, &grant.patient, &grant.grantee, not_before);
+
+    let purposes = consent::get_grant_purposes(env, &grant.patient, &grant.grantee);
+    let duration_seconds = expires_at.saturating_sub(env.ledger().timestamp());
+    events::publish_access_granted(env, actor, &grant, purposes, duration_seconds);
+}
+
+/// Undo what an appointment's confirmation did to the provider's grant,
+/// unless the patient has since replaced it: a grant it created is revoked,
+/// and a grant it extended gets its earlier window back
+fn end_appointment_grant(env: &Env, actor: Address, appointment: &Appointment) {
+    if appointment.grant_expires_at == 0 {
+        return;
+    }
+    let grant = match get_unexpired_grant(env, &appointment.patient, &appointment.provider) {
+        Some(grant) if grant.expires_at == appointment.grant_expires_at => grant,
+        _ => return,
+    };
+
+    if appointment.prior_expires_at == 0 {
+        revoke_patient_access(
+            env,
+            actor,
+            appointment.patient.clone(),
+            appointment.provider.clone(),
+        );
+    } else {
+        set_grant_window(
+            env,
+            actor,
+            grant,
+            appointment.prior_not_before,
+            appointment.prior_expires_at,
+        );
+    }
+}
+
+/// Whether every line of `claim` still names an existing record of its
+/// patient by that provider, unchanged since the claim was submitted
+fn claim_matches_records(env: &Env, claim: &Claim) -> bool {
+    claim.lines.iter().all(|line| {
+        VisionRecordsContract::get_record(env.clone(), line.record_id).is_ok_and(|record| {
+            record.patient == claim.patient
+                && record.provider == claim.provider
+                && claim::line_matches(env, &line, &record)
+        })
+    })
+}
+
+/// Store key envelopes for `grantee`, keyed by record ID. The grantee must
+/// have registered a public key, and every record must belong to `patient`.
+fn store_key_envelopes(
+    env: &Env,
+    caller: Address,
+    patient: Address,
+    grantee: Address,
+    envelopes: Map<u64, Bytes>,
+) -> Result<(), ContractError> {
+    if keys::get_public_key(env, &grantee).is_none() || envelopes.is_empty() {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let mut record_ids = Vec::new(env);
+    for (record_id, wrapped_key) in envelopes.iter() {
+        let record = VisionRecordsContract::get_record(env.clone(), record_id)?;
+        if record.patient != patient || wrapped_key.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+        let envelope = KeyEnvelope {
+            record_id,
+            grantee: grantee.clone(),
+            wrapped_key,
+            epoch: keys::get_record_epoch(env, record_id),
+            created_at: env.ledger().timestamp(),
+            stale: false,
+        };
+        keys::set_envelope(env, &patient, &envelope);
+        record_ids.push_back(record_id);
+    }
+
+    events::publish_key_envelopes_set(env, caller, patient, grantee, record_ids);
+
+    Ok(())
+}
+
+/// Whether `caller` may read `record` for `purpose`. The patient always may.
+/// Anyone else needs a purpose the patient's directive doesn't deny, and
+/// either a patient-wide grant carrying that purpose, a record-scoped grant
+/// (which only covers treatment), a group grant to a group they currently
+/// belong to, or their own standing as the record's provider,
+/// an on-duty provider of its owning organization or a holder of
+/// `ReadAnyRecord`, limited to the directive's default purposes.
+fn can_read_record(
+    env: &Env,
+    caller: &Address,
+    record: &VisionRecord,
+    purpose: &PurposeOfUse,
+) -> bool {
+    if *caller == record.patient {
+        return true;
+    }
+
+    let directive = consent::get_directive(env, &record.patient);
+    if directive.denied_purposes.contains(purpose) {
+        return false;
+    }
+
+    if let Some(grant) = get_active_grant(env, &record.patient, caller) {
+        if grant.level != AccessLevel::None
+            && consent::get_grant_purposes(env, &record.patient, caller).contains(purpose)
+        {
+            return true;
+        }
+    }
+
+    for grant in groups::get_member_grants(env, &record.patient, caller).iter() {
+        if grant.level != AccessLevel::None && grant.purposes.contains(purpose) {
+            return true;
+        }
+    }
+
+    if *purpose == PurposeOfUse::Treatment
+        && referral::get_record_access(env, record.id, caller) != AccessLevel::None
+    {
+        return true;
+    }
+
+    let owning_org_provider = organization::get_record_org(env, record.id)
+        .is_some_and(|org_id| organization::is_on_duty_provider(env, org_id, caller));
+
+    (*caller == record.provider
+        || owning_org_provider
+        || rbac::has_permission(env, caller, &Permission::ReadAnyRecord))
+        && directive.default_purposes.contains(purpose)
+}
+
+/// Whether `patient`'s consent directive still lets their opted-in records
+/// reach researchers. The study opt-in is the consent, so only an explicit
+/// denial of research withholds them.
+fn permits_research(env: &Env, patient: &Address) -> bool {
+    !consent::get_directive(env, patient)
+        .denied_purposes
+        .contains(PurposeOfUse::Research)
+}
+
+/// `permissions` with repeats dropped, first occurrence kept
+fn dedup_permissions(env: &Env, permissions: &Vec<Permission>) -> Vec<Permission> {
+    let mut unique = Vec::new(env);
+    for permission in permissions.iter() {
+        if !unique.contains(&permission) {
+            unique.push_back(permission);
+        }
+    }
+    unique
+}
+
+/// Count a call to `action` against the caller's and the patient's rate
+/// limits, if any are configured
+fn check_rate_limits(
+    env: &Env,
+    action: RateLimitedAction,
+    caller: &Address,
+    patient: &Address,
+) -> Result<(), ContractError> {
+    if !rate_limit::consume(env, &action, &RateLimitScope::Caller, caller)
+        || !rate_limit::consume(env, &action, &RateLimitScope::Patient, patient)
+    {
+        return Err(ContractError::RateLimited);
+    }
+    Ok(())
+}
+
+/// Store a new record and index it under the patient. Callers check the
+/// writer's authority first; rate limits are checked here.
+#[allow(clippy::arithmetic_side_effects)]
+fn store_record(
+    env: &Env,
+    caller: Address,
+    patient: Address,
+    provider: Address,
+    record_type: RecordType,
+    data_hash: String,
+) -> Result<u64, ContractError> {
+    check_rate_limits(env, RateLimitedAction::AddRecord, &caller, &patient)?;
+
+    // Generate record ID
+    let counter_key = symbol_short!("REC_CTR");
+    let record_id: u64 = env.storage().instance().get(&counter_key).unwrap_or(0) + 1;
+    env.storage().instance().set(&counter_key, &record_id);
+
+    let record = VisionRecord {
+        id: record_id,
+        patient: patient.clone(),
+        provider: provider.clone(),
+        record_type: record_type.clone(),
+        data_hash,
+        created_at: env.ledger().timestamp(),
+        updated_at: env.ledger().timestamp(),
+    };
+
+    let key = (symbol_short!("RECORD"), record_id);
+    env.storage().persistent().set(&key, &record);
+    merkle::append(env, &record);
+
+    // New records are encrypted under the patient's current key epoch
+    let epoch = keys::get_key_epoch(env, &patient);
+    if epoch > 0 {
+        keys::set_record_epoch(env, record_id, epoch);
+    }
+
+    // Add to patient's record list
+    let patient_key = (symbol_short!("PAT_REC"), patient.clone());
+    let mut patient_records: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&patient_key)
+        .unwrap_or(Vec::new(env));
+    patient_records.push_back(record_id);
+    env.storage()
+        .persistent()
+        .set(&patient_key, &patient_records);
+
+    // A matching record earmarks the patient's oldest held payment for it;
+    // the provider collects it with `release_escrow`
+    if let Some(mut escrow) = escrow::find_match(env, &patient, &provider, &record_type) {
+        escrow::mark_matched(env, &mut escrow, record_id);
+        events::publish_escrow_settled(env, caller.clone(), &escrow);
+    }
+
+    events::publish_record_added(env, caller, record_id, patient, provider, record_type);
+
+    Ok(record_id)
+}
+
+/// Suspend or reinstate a provider's credential as an accredited verifier
+fn set_credential_status(
+    env: &Env,
+    verifier: Address,
+    provider: Address,
+    status: CredentialStatus,
+) -> Result<(), ContractError> {
+    verifier.require_auth();
+
+    if !rbac::has_permission(env, &verifier, &Permission::VerifyCredentials) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let mut credential =
+        credentials::get_credential(env, &provider).ok_or(ContractError::CredentialNotFound)?;
+    credential.status = status.clone();
+    credentials::set_credential(env, &credential);
+
+    events::publish_credential_status_changed(env, verifier.clone(), provider, verifier, status);
+
+    Ok(())
 }
 
 #[contract]
@@ -101,9 +634,10 @@ impl VisionRecordsContract {
 
         env.storage().instance().set(&ADMIN, &admin);
         env.storage().instance().set(&INITIALIZED, &true);
-        rbac::assign_role(&env, admin.clone(), Role::Admin, 0);
+        rbac::init_role_permissions(&env);
+        rbac::assign_role(&env, admin.clone(), admin.clone(), Role::Admin, 0);
 
-        events::publish_initialized(&env, admin);
+        events::publish_initialized(&env, admin.clone(), admin);
 
         Ok(())
     }
@@ -134,6 +668,7 @@ impl VisionRecordsContract {
         if !rbac::has_permission(&env, &caller, &Permission::ManageUsers) {
             return Err(ContractError::Unauthorized);
         }
+        check_rate_limits(&env, RateLimitedAction::RegisterUser, &caller, &user)?;
 
         let user_data = User {
             address: user.clone(),
@@ -145,9 +680,9 @@ impl VisionRecordsContract {
 
         let key = (symbol_short!("USER"), user.clone());
         env.storage().persistent().set(&key, &user_data);
-        rbac::assign_role(&env, user.clone(), role.clone(), 0);
+        rbac::assign_role(&env, caller.clone(), user.clone(), role.clone(), 0);
 
-        events::publish_user_registered(&env, user, role, name);
+        events::publish_user_registered(&env, caller, user, role, name);
 
         Ok(())
     }
@@ -162,7 +697,6 @@ impl VisionRecordsContract {
     }
 
     /// Add a vision record
-    #[allow(clippy::arithmetic_side_effects)]
     pub fn add_record(
         env: Env,
         caller: Address,
@@ -173,52 +707,16 @@ impl VisionRecordsContract {
     ) -> Result<u64, ContractError> {
         caller.require_auth();
 
-        let has_perm = if caller == provider {
-            rbac::has_permission(&env, &caller, &Permission::WriteRecord)
-        } else {
-            rbac::has_delegated_permission(&env, &provider, &caller, &Permission::WriteRecord)
-        };
-
-        if !has_perm && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+        if !can_write_for_provider(&env, &caller, &provider) {
             return Err(ContractError::Unauthorized);
         }
 
-        // Generate record ID
-        let counter_key = symbol_short!("REC_CTR");
-        let record_id: u64 = env.storage().instance().get(&counter_key).unwrap_or(0) + 1;
-        env.storage().instance().set(&counter_key, &record_id);
-
-        let record = VisionRecord {
-            id: record_id,
-            patient: patient.clone(),
-            provider: provider.clone(),
-            record_type: record_type.clone(),
-            data_hash,
-            created_at: env.ledger().timestamp(),
-            updated_at: env.ledger().timestamp(),
-        };
-
-        let key = (symbol_short!("RECORD"), record_id);
-        env.storage().persistent().set(&key, &record);
-
-        // Add to patient's record list
-        let patient_key = (symbol_short!("PAT_REC"), patient.clone());
-        let mut patient_records: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&patient_key)
-            .unwrap_or(Vec::new(&env));
-        patient_records.push_back(record_id);
-        env.storage()
-            .persistent()
-            .set(&patient_key, &patient_records);
-
-        events::publish_record_added(&env, record_id, patient, provider, record_type);
-
-        Ok(record_id)
+        store_record(&env, caller, patient, provider, record_type, data_hash)
     }
 
-    /// Get a vision record by ID
+    /// Get a vision record by ID. This is a public, unaudited view: it needs
+    /// no permission and returns the data hash to any caller, including
+    /// Auditors and Insurers. Payloads stay protected by their encryption.
     pub fn get_record(env: Env, record_id: u64) -> Result<VisionRecord, ContractError> {
         let key = (symbol_short!("RECORD"), record_id);
         env.storage()
@@ -227,6 +725,104 @@ impl VisionRecordsContract {
             .ok_or(ContractError::RecordNotFound)
     }
 
+    /// Get a record's metadata without its data hash. Requires `ReadRecordMetadata`.
+    pub fn get_record_metadata(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+    ) -> Result<RecordMetadata, ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::ReadRecordMetadata) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let record = Self::get_record(env, record_id)?;
+        Ok(RecordMetadata {
+            id: record.id,
+            patient: record.patient,
+            provider: record.provider,
+            record_type: record.record_type,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        })
+    }
+
+    /// Confirm that `record_id` exists, belongs to `patient` and is of
+    /// `record_type`, without revealing anything else about it. Requires
+    /// `VerifyRecordExistence`.
+    pub fn verify_record_existence(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+        patient: Address,
+        record_type: RecordType,
+    ) -> Result<bool, ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::VerifyRecordExistence) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Ok(match Self::get_record(env, record_id) {
+            Ok(record) => record.patient == patient && record.record_type == record_type,
+            Err(_) => false,
+        })
+    }
+
+    /// Get the Merkle root committing to the patient's records. Each leaf
+    /// commits to `(record_id, data_hash, record_type, created_at)`.
+    pub fn get_merkle_root(env: Env, patient: Address) -> BytesN<32> {
+        merkle::get_root(&env, &patient)
+    }
+
+    /// Get an inclusion proof for a record against its patient's current root
+    pub fn get_merkle_proof(env: Env, record_id: u64) -> Result<MerkleProof, ContractError> {
+        let record = Self::get_record(env.clone(), record_id)?;
+        let index = merkle::get_leaf_index(&env, record_id).ok_or(ContractError::RecordNotFound)?;
+        Ok(merkle::proof(&env, &record.patient, index))
+    }
+
+    /// Check that `leaf` is part of the patient's chart, by folding it up
+    /// through `proof` and comparing with the patient's current root
+    pub fn verify_record_inclusion(
+        env: Env,
+        patient: Address,
+        leaf: RecordLeaf,
+        proof: MerkleProof,
+    ) -> bool {
+        let size = merkle::get_size(&env, &patient);
+        proof.leaf_index < size
+            && proof.siblings.len() == merkle::depth(size)
+            && merkle::compute_root(&env, &leaf, &proof) == merkle::get_root(&env, &patient)
+    }
+
+    /// Commit the patient's records that predate the Merkle tree, appending
+    /// them in record order. Callable by the patient or a `SystemAdmin`.
+    /// Returns how many records were added.
+    pub fn backfill_merkle_tree(
+        env: Env,
+        caller: Address,
+        patient: Address,
+    ) -> Result<u32, ContractError> {
+        caller.require_auth();
+
+        if caller != patient && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let mut added = 0u32;
+        for record_id in Self::get_patient_records(env.clone(), patient.clone()).iter() {
+            if merkle::get_leaf_index(&env, record_id).is_some() {
+                continue;
+            }
+            let record = Self::get_record(env.clone(), record_id)?;
+            merkle::append(&env, &record);
+            added = added.saturating_add(1);
+        }
+        Ok(added)
+    }
+
     /// Get all records for a patient
     pub fn get_patient_records(env: Env, patient: Address) -> Vec<u64> {
         let key = (symbol_short!("PAT_REC"), patient);
@@ -236,8 +832,7 @@ impl VisionRecordsContract {
             .unwrap_or(Vec::new(&env))
     }
 
-    /// Grant access to a user
-    #[allow(clippy::arithmetic_side_effects)]
+    /// Grant access to a user for the purposes in the patient's consent directive
     pub fn grant_access(
         env: Env,
         caller: Address,
@@ -246,47 +841,91 @@ impl VisionRecordsContract {
         level: AccessLevel,
         duration_seconds: u64,
     ) -> Result<(), ContractError> {
-        caller.require_auth();
+        grant_patient_access(
+            &env,
+            caller,
+            patient,
+            grantee,
+            level,
+            duration_seconds,
+            None,
+        )
+    }
 
-        let has_perm = if caller == patient {
-            true // Patient manages own access
-        } else {
-            rbac::has_delegated_permission(&env, &patient, &caller, &Permission::ManageAccess)
-                || rbac::has_permission(&env, &caller, &Permission::SystemAdmin)
+    /// Grant access limited to explicit purposes of use. Fails with
+    /// `AccessDenied` if the patient's consent directive denies any of them.
+    pub fn grant_access_for_purposes(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        grantee: Address,
+        level: AccessLevel,
+        duration_seconds: u64,
+        purposes: Vec<PurposeOfUse>,
+    ) -> Result<(), ContractError> {
+        grant_patient_access(
+            &env,
+            caller,
+            patient,
+            grantee,
+            level,
+            duration_seconds,
+            Some(purposes),
+        )
+    }
+
+    /// Check access level. Takes the highest of `grantee`'s own grant and the
+    /// group grants of groups they currently belong to.
+    pub fn check_access(env: Env, patient: Address, grantee: Address) -> AccessLevel {
+        let mut level = match get_active_grant(&env, &patient, &grantee) {
+            Some(grant) => grant.level,
+            None => AccessLevel::None,
         };
+        for grant in groups::get_member_grants(&env, &patient, &grantee).iter() {
+            if access_rank(&grant.level) > access_rank(&level) {
+                level = grant.level;
+            }
+        }
+        level
+    }
 
-        if !has_perm {
-            return Err(ContractError::Unauthorized);
+    /// Get the purposes of use `grantee`'s unexpired grant covers
+    pub fn get_grant_purposes(env: Env, patient: Address, grantee: Address) -> Vec<PurposeOfUse> {
+        match get_active_grant(&env, &patient, &grantee) {
+            Some(_) => consent::get_grant_purposes(&env, &patient, &grantee),
+            None => Vec::new(&env),
         }
+    }
 
-        let expires_at = env.ledger().timestamp() + duration_seconds;
-        let grant = AccessGrant {
+    /// Set the patient's consent directive. Existing grants keep their
+    /// purposes, but reads for a newly denied purpose are refused at once.
+    pub fn set_consent_directive(
+        env: Env,
+        patient: Address,
+        default_purposes: Vec<PurposeOfUse>,
+        denied_purposes: Vec<PurposeOfUse>,
+    ) -> Result<(), ContractError> {
+        patient.require_auth();
+
+        let directive = ConsentDirective {
             patient: patient.clone(),
-            grantee: grantee.clone(),
-            level: level.clone(),
-            granted_at: env.ledger().timestamp(),
-            expires_at,
+            default_purposes,
+            denied_purposes,
+            updated_at: env.ledger().timestamp(),
         };
+        if !consent::is_valid_directive(&directive) {
+            return Err(ContractError::InvalidInput);
+        }
+        consent::set_directive(&env, &directive);
 
-        let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
-        env.storage().persistent().set(&key, &grant);
-
-        events::publish_access_granted(&env, patient, grantee, level, duration_seconds, expires_at);
+        events::publish_consent_directive_set(&env, patient, &directive);
 
         Ok(())
     }
 
-    /// Check access level
-    pub fn check_access(env: Env, patient: Address, grantee: Address) -> AccessLevel {
-        let key = (symbol_short!("ACCESS"), patient, grantee);
-
-        if let Some(grant) = env.storage().persistent().get::<_, AccessGrant>(&key) {
-            if grant.expires_at > env.ledger().timestamp() {
-                return grant.level;
-            }
-        }
-
-        AccessLevel::None
+    /// Get the patient's consent directive, or the default if none was set
+    pub fn get_consent_directive(env: Env, patient: Address) -> ConsentDirective {
+        consent::get_directive(&env, &patient)
     }
 
     /// Revoke access
@@ -297,10 +936,7 @@ impl VisionRecordsContract {
     ) -> Result<(), ContractError> {
         patient.require_auth();
 
-        let key = (symbol_short!("ACCESS"), patient.clone(), grantee.clone());
-        env.storage().persistent().remove(&key);
-
-        events::publish_access_revoked(&env, patient, grantee);
+        revoke_patient_access(&env, patient.clone(), patient, grantee);
 
         Ok(())
     }
@@ -316,6 +952,11 @@ impl VisionRecordsContract {
         1
     }
 
+    /// Version of the event payload layout, see `events::EVENT_SCHEMA_VERSION`
+    pub fn get_event_schema_version() -> u32 {
+        events::EVENT_SCHEMA_VERSION
+    }
+
     // ======================== RBAC Endpoints ========================
 
     pub fn grant_custom_permission(
@@ -325,10 +966,13 @@ impl VisionRecordsContract {
         permission: Permission,
     ) -> Result<(), ContractError> {
         caller.require_auth();
-        if !rbac::has_permission(&env, &caller, &Permission::ManageUsers) {
+        if !rbac::has_permission(&env, &caller, &Permission::ManageUsers)
+            || (rbac::is_privileged_permission(&permission)
+                && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin))
+        {
             return Err(ContractError::Unauthorized);
         }
-        rbac::grant_custom_permission(&env, user, permission)
+        rbac::grant_custom_permission(&env, caller, user, permission)
             .map_err(|_| ContractError::UserNotFound)?;
         Ok(())
     }
@@ -343,7 +987,7 @@ impl VisionRecordsContract {
         if !rbac::has_permission(&env, &caller, &Permission::ManageUsers) {
             return Err(ContractError::Unauthorized);
         }
-        rbac::revoke_custom_permission(&env, user, permission)
+        rbac::revoke_custom_permission(&env, caller, user, permission)
             .map_err(|_| ContractError::UserNotFound)?;
         Ok(())
     }
@@ -360,7 +1004,2130 @@ impl VisionRecordsContract {
         Ok(())
     }
 
-    pub fn check_permission(env: Env, user: Address, permission: Permission) -> bool {
-        rbac::has_permission(&env, &user, &permission)
+    /// Give a user an additional role with its own expiry (0 for none).
+    /// Requires `ManageUsers`, and `SystemAdmin` for a role currently
+    /// carrying `SystemAdmin`, `ManageUsers` or `VerifyCredentials`.
+    pub fn add_role(
+        env: Env,
+        caller: Address,
+        user: Address,
+        role: Role,
+        expires_at: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::ManageUsers) {
+            return Err(ContractError::Unauthorized);
+        }
+        let privileged = rbac::get_role_permissions(&env, &role)
+            .iter()
+            .any(|permission| rbac::is_privileged_permission(&permission));
+        if privileged && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Err(ContractError::Unauthorized);
+        }
+        if expires_at != 0 && expires_at <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        rbac::add_role(&env, caller, user, role, expires_at);
+        Ok(())
+    }
+
+    /// Remove an additional role. The role from `register_user` is not affected.
+    /// Requires `ManageUsers`.
+    pub fn remove_role(
+        env: Env,
+        caller: Address,
+        user: Address,
+        role: Role,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::ManageUsers) {
+            return Err(ContractError::Unauthorized);
+        }
+        if !rbac::remove_role(&env, caller, user, role) {
+            return Err(ContractError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// Get every role a user currently holds
+    pub fn get_user_roles(env: Env, user: Address) -> Vec<Role> {
+        rbac::get_active_roles(&env, &user)
+    }
+
+    /// Define a named custom role with its own permission set. Requires `SystemAdmin`.
+    pub fn create_custom_role(
+        env: Env,
+        caller: Address,
+        name: String,
+        permissions: Vec<Permission>,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Err(ContractError::Unauthorized);
+        }
+        if name.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let role = CustomRole {
+            id: rbac::next_custom_role_id(&env),
+            name,
+            permissions: dedup_permissions(&env, &permissions),
+            created_by: caller.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+        rbac::save_custom_role(&env, &role);
+
+        events::publish_custom_role_set(&env, caller, &role);
+
+        Ok(role.id)
+    }
+
+    /// Replace the permissions of a custom role. Requires `SystemAdmin`.
+    pub fn set_custom_role_permissions(
+        env: Env,
+        caller: Address,
+        role_id: u64,
+        permissions: Vec<Permission>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let mut role =
+            rbac::get_custom_role(&env, role_id).ok_or(ContractError::CustomRoleNotFound)?;
+        role.permissions = dedup_permissions(&env, &permissions);
+        rbac::save_custom_role(&env, &role);
+
+        events::publish_custom_role_set(&env, caller, &role);
+
+        Ok(())
+    }
+
+    /// Get a custom role by ID
+    pub fn get_custom_role(env: Env, role_id: u64) -> Result<CustomRole, ContractError> {
+        rbac::get_custom_role(&env, role_id).ok_or(ContractError::CustomRoleNotFound)
+    }
+
+    /// Give a user a custom role, held alongside their enum role. A user has
+    /// a single custom role slot, so this replaces any custom role they had. Requires `ManageUsers`, and `SystemAdmin`
+    /// for a role carrying `SystemAdmin`, `ManageUsers` or `VerifyCredentials`.
+    pub fn assign_custom_role(
+        env: Env,
+        caller: Address,
+        user: Address,
+        role_id: u64,
+        expires_at: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::ManageUsers) {
+            return Err(ContractError::Unauthorized);
+        }
+        let role = rbac::get_custom_role(&env, role_id).ok_or(ContractError::CustomRoleNotFound)?;
+        let privileged = role
+            .permissions
+            .iter()
+            .any(|permission| rbac::is_privileged_permission(&permission));
+        if privileged && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Err(ContractError::Unauthorized);
+        }
+        if expires_at != 0 && expires_at <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        rbac::assign_custom_role(&env, caller, user, role_id, expires_at);
+        Ok(())
+    }
+
+    /// Remove a user's custom role. Requires `ManageUsers`.
+    pub fn revoke_custom_role(
+        env: Env,
+        caller: Address,
+        user: Address,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::ManageUsers) {
+            return Err(ContractError::Unauthorized);
+        }
+        if !rbac::revoke_custom_role(&env, caller, user) {
+            return Err(ContractError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// Delegate a custom role the delegator currently holds
+    pub fn delegate_custom_role(
+        env: Env,
+        delegator: Address,
+        delegatee: Address,
+        role_id: u64,
+        expires_at: u64,
+    ) -> Result<(), ContractError> {
+        delegator.require_auth();
+
+        match rbac::get_active_custom_assignment(&env, &delegator) {
+            Some(assignment) if assignment.role_id == role_id => {}
+            _ => return Err(ContractError::Unauthorized),
+        }
+
+        rbac::delegate_custom_role(&env, delegator, delegatee, role_id, expires_at);
+        Ok(())
+    }
+
+    pub fn check_permission(env: Env, user: Address, permission: Permission) -> bool {
+        rbac::has_permission(&env, &user, &permission)
+    }
+
+    /// Replace the permissions a role carries. Requires `SystemAdmin`. The
+    /// `Admin` role must keep `SystemAdmin`, so the matrix stays editable.
+    pub fn set_role_permissions(
+        env: Env,
+        caller: Address,
+        role: Role,
+        permissions: Vec<Permission>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Err(ContractError::Unauthorized);
+        }
+        if role == Role::Admin && !permissions.contains(Permission::SystemAdmin) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        rbac::set_role_permissions(&env, caller, role, dedup_permissions(&env, &permissions));
+
+        Ok(())
+    }
+
+    /// Get the permissions a role currently carries
+    pub fn get_role_permissions(env: Env, role: Role) -> Vec<Permission> {
+        rbac::get_role_permissions(&env, &role)
+    }
+
+    /// Remove a user's expired role assignment and announce it. Callable by
+    /// anyone, so indexers see expiry as an explicit event.
+    pub fn expire_role(env: Env, caller: Address, user: Address) -> Result<(), ContractError> {
+        caller.require_auth();
+        if !rbac::expire_role(&env, caller, user) {
+            return Err(ContractError::InvalidInput);
+        }
+        Ok(())
+    }
+
+    // ======================== Record Linking Endpoints ========================
+
+    /// Link `source_id` to the record it builds on. The caller must be able to
+    /// write records as the provider of the source record.
+    pub fn link_records(
+        env: Env,
+        caller: Address,
+        source_id: u64,
+        target_id: u64,
+        link_type: LinkType,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let source = Self::get_record(env.clone(), source_id)?;
+        let target = Self::get_record(env.clone(), target_id)?;
+
+        if !can_write_for_provider(&env, &caller, &source.provider) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if source.patient != target.patient
+            || !links::is_valid_link(&source.record_type, &target.record_type, &link_type)
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let link = RecordLink {
+            source_id,
+            target_id,
+            link_type: link_type.clone(),
+            created_by: caller.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+        if !links::add_link(&env, &link) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        events::publish_records_linked(&env, caller, source_id, target_id, link_type);
+
+        Ok(())
+    }
+
+    /// Get the direct links of a record, in both directions
+    pub fn get_record_links(env: Env, record_id: u64) -> Vec<RecordLink> {
+        links::get_links(&env, record_id)
+    }
+
+    /// Get every record and link reachable from a record
+    pub fn get_linked_graph(env: Env, record_id: u64) -> Result<LinkedGraph, ContractError> {
+        Self::get_record(env.clone(), record_id)?;
+        Ok(links::get_linked_graph(&env, record_id))
+    }
+
+    /// Open a new episode of care for a patient
+    pub fn create_episode(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        title: String,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        if !can_write_for_provider(&env, &caller, &caller) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let episode_id = links::create_episode(&env, patient.clone(), title, caller.clone());
+
+        events::publish_episode_created(&env, caller, episode_id, patient);
+
+        Ok(episode_id)
+    }
+
+    /// Group a record into an episode of care
+    pub fn add_record_to_episode(
+        env: Env,
+        caller: Address,
+        episode_id: u64,
+        record_id: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let episode = links::get_episode(&env, episode_id).ok_or(ContractError::EpisodeNotFound)?;
+        let record = Self::get_record(env.clone(), record_id)?;
+
+        if !can_write_for_provider(&env, &caller, &record.provider) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if record.patient != episode.patient
+            || !links::add_record_to_episode(&env, episode, record_id)
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        events::publish_episode_record_added(&env, caller, episode_id, record_id);
+
+        Ok(())
+    }
+
+    /// Get an episode of care by ID
+    pub fn get_episode(env: Env, episode_id: u64) -> Result<Episode, ContractError> {
+        links::get_episode(&env, episode_id).ok_or(ContractError::EpisodeNotFound)
+    }
+
+    /// Get the episode a record belongs to, if any
+    pub fn get_record_episode(env: Env, record_id: u64) -> Option<u64> {
+        links::get_record_episode(&env, record_id)
+    }
+
+    /// Get all episode IDs for a patient
+    pub fn get_patient_episodes(env: Env, patient: Address) -> Vec<u64> {
+        links::get_patient_episodes(&env, &patient)
+    }
+
+    // ======================== Prescription Endpoints ========================
+
+    /// Attach structured Rx metadata to an existing `RecordType::Prescription`
+    /// record. The caller must be able to write records as its provider.
+    pub fn add_prescription(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+        lens_type: LensType,
+        left_eye: EyeRx,
+        right_eye: EyeRx,
+        expires_at: u64,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        let record = Self::get_record(env.clone(), record_id)?;
+
+        if !can_write_for_provider(&env, &caller, &record.provider) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if record.record_type != RecordType::Prescription
+            || expires_at <= env.ledger().timestamp()
+            || !prescription::is_valid_eye_rx(&left_eye, &lens_type)
+            || !prescription::is_valid_eye_rx(&right_eye, &lens_type)
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let rx = Prescription {
+            id: prescription::next_prescription_id(&env),
+            record_id,
+            patient: record.patient.clone(),
+            provider: record.provider.clone(),
+            lens_type: lens_type.clone(),
+            left_eye,
+            right_eye,
+            issued_at: env.ledger().timestamp(),
+            expires_at,
+        };
+        prescription::save_prescription(&env, &rx);
+
+        events::publish_prescription_issued(&env, caller, &rx);
+
+        Ok(rx.id)
+    }
+
+    /// Get a structured prescription by ID
+    pub fn get_prescription(env: Env, prescription_id: u64) -> Result<Prescription, ContractError> {
+        prescription::get_prescription(&env, prescription_id)
+            .ok_or(ContractError::PrescriptionNotFound)
+    }
+
+    /// Get all prescription IDs for a patient
+    pub fn get_patient_prescriptions(env: Env, patient: Address) -> Vec<u64> {
+        prescription::get_patient_prescriptions(&env, &patient)
+    }
+
+    /// Confirm that a prescription belongs to `patient`, is backed by an
+    /// existing record and has not expired. Lets dispensers check an Rx
+    /// without being granted access to the patient's records.
+    pub fn verify_prescription(env: Env, prescription_id: u64, patient: Address) -> bool {
+        match prescription::get_prescription(&env, prescription_id) {
+            Some(rx) => {
+                rx.patient == patient
+                    && prescription::is_unexpired(&env, &rx)
+                    && Self::get_record(env.clone(), rx.record_id).is_ok()
+            }
+            None => false,
+        }
+    }
+
+    // ======================== Examination Endpoints ========================
+
+    /// Attach structured results to an existing `RecordType::Examination`
+    /// record. Each record carries at most one set of results.
+    pub fn add_examination(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+        left_eye: EyeExamination,
+        right_eye: EyeExamination,
+        imaging_refs: Vec<String>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let record = Self::get_record(env.clone(), record_id)?;
+
+        if !can_write_for_provider(&env, &caller, &record.provider) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if record.record_type != RecordType::Examination
+            || examination::get_examination(&env, record_id).is_some()
+            || !examination::is_valid_eye_examination(&left_eye)
+            || !examination::is_valid_eye_examination(&right_eye)
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let exam = ExaminationResult {
+            record_id,
+            patient: record.patient.clone(),
+            provider: record.provider.clone(),
+            left_eye,
+            right_eye,
+            imaging_refs,
+            recorded_at: env.ledger().timestamp(),
+        };
+        examination::save_examination(&env, &exam);
+
+        events::publish_examination_recorded(
+            &env,
+            caller,
+            record_id,
+            record.patient,
+            record.provider,
+        );
+
+        Ok(())
+    }
+
+    /// Get the structured results of an examination record
+    pub fn get_examination(env: Env, record_id: u64) -> Result<ExaminationResult, ContractError> {
+        examination::get_examination(&env, record_id).ok_or(ContractError::ExaminationNotFound)
+    }
+
+    /// Get the IDs of all examination records with structured results for a patient
+    pub fn get_patient_examinations(env: Env, patient: Address) -> Vec<u64> {
+        examination::get_patient_examinations(&env, &patient)
+    }
+
+    /// Get a per-eye series of `metric` across a patient's examinations, oldest first
+    pub fn get_exam_trend(env: Env, patient: Address, metric: ExamMetric) -> Vec<TrendPoint> {
+        examination::get_trend(&env, &patient, &metric)
+    }
+
+    // ======================== Referral Endpoints ========================
+
+    /// Refer a patient from an optometrist to an ophthalmologist, both holding
+    /// a valid credential for their role. The specialist is automatically
+    /// granted time-limited read access to the referenced records only.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn create_referral(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        specialist: Address,
+        reason: String,
+        urgency: ReferralUrgency,
+        record_ids: Vec<u64>,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_role(&env, &caller, &Role::Optometrist)
+            || !credentials::has_valid_credential(&env, &caller, &Role::Optometrist)
+        {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if !rbac::has_role(&env, &specialist, &Role::Ophthalmologist)
+            || !credentials::has_valid_credential(&env, &specialist, &Role::Ophthalmologist)
+            || record_ids.is_empty()
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let caller_has_grant =
+            Self::check_access(env.clone(), patient.clone(), caller.clone()) != AccessLevel::None;
+        for record_id in record_ids.iter() {
+            let record = Self::get_record(env.clone(), record_id)?;
+            if record.patient != patient {
+                return Err(ContractError::InvalidInput);
+            }
+            if record.provider != caller && !caller_has_grant {
+                return Err(ContractError::Unauthorized);
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        let access_expires_at = now + referral::REFERRAL_ACCESS_DURATION;
+        let referral = Referral {
+            id: referral::next_referral_id(&env),
+            patient: patient.clone(),
+            referrer: caller.clone(),
+            specialist: specialist.clone(),
+            reason,
+            urgency: urgency.clone(),
+            record_ids: record_ids.clone(),
+            status: ReferralStatus::Pending,
+            created_at: now,
+            updated_at: now,
+            access_expires_at,
+            outcome_record_id: None,
+        };
+        referral::create_referral(&env, &referral);
+
+        for record_id in record_ids.iter() {
+            referral::grant_record_access(
+                &env,
+                record_id,
+                &specialist,
+                AccessLevel::Read,
+                access_expires_at,
+                referral.id,
+            );
+        }
+
+        events::publish_referral_created(&env, caller, &referral);
+
+        Ok(referral.id)
+    }
+
+    /// Accept a pending referral as the receiving specialist
+    pub fn accept_referral(
+        env: Env,
+        caller: Address,
+        referral_id: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut referral = Self::get_referral(env.clone(), referral_id)?;
+        if referral.specialist != caller {
+            return Err(ContractError::Unauthorized);
+        }
+        if referral.status != ReferralStatus::Pending {
+            return Err(ContractError::InvalidInput);
+        }
+
+        referral.status = ReferralStatus::Accepted;
+        referral.updated_at = env.ledger().timestamp();
+        referral::save_referral(&env, &referral);
+
+        events::publish_referral_updated(
+            &env,
+            caller,
+            referral_id,
+            referral.patient,
+            referral.specialist,
+            ReferralStatus::Accepted,
+            None,
+        );
+
+        Ok(())
+    }
+
+    /// Decline a pending referral. The specialist's scoped read access to the
+    /// referenced records is withdrawn, except where another referral still
+    /// relies on it.
+    pub fn decline_referral(
+        env: Env,
+        caller: Address,
+        referral_id: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut referral = Self::get_referral(env.clone(), referral_id)?;
+        if referral.specialist != caller {
+            return Err(ContractError::Unauthorized);
+        }
+        if referral.status != ReferralStatus::Pending {
+            return Err(ContractError::InvalidInput);
+        }
+
+        for record_id in referral.record_ids.iter() {
+            referral::revoke_record_access(&env, record_id, &referral.specialist, referral_id);
+        }
+
+        referral.status = ReferralStatus::Declined;
+        referral.updated_at = env.ledger().timestamp();
+        referral::save_referral(&env, &referral);
+
+        events::publish_referral_updated(
+            &env,
+            caller,
+            referral_id,
+            referral.patient,
+            referral.specialist,
+            ReferralStatus::Declined,
+            None,
+        );
+
+        Ok(())
+    }
+
+    /// Complete an accepted referral by linking the specialist's resulting
+    /// `Diagnosis` or `Surgery` record
+    pub fn complete_referral(
+        env: Env,
+        caller: Address,
+        referral_id: u64,
+        outcome_record_id: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut referral = Self::get_referral(env.clone(), referral_id)?;
+        if referral.specialist != caller {
+            return Err(ContractError::Unauthorized);
+        }
+        if referral.status != ReferralStatus::Accepted {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let outcome = Self::get_record(env.clone(), outcome_record_id)?;
+        if outcome.patient != referral.patient
+            || outcome.provider != referral.specialist
+            || (outcome.record_type != RecordType::Diagnosis
+                && outcome.record_type != RecordType::Surgery)
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        referral.status = ReferralStatus::Completed;
+        referral.outcome_record_id = Some(outcome_record_id);
+        referral.updated_at = env.ledger().timestamp();
+        referral::save_referral(&env, &referral);
+
+        events::publish_referral_updated(
+            &env,
+            caller,
+            referral_id,
+            referral.patient,
+            referral.specialist,
+            ReferralStatus::Completed,
+            Some(outcome_record_id),
+        );
+
+        Ok(())
+    }
+
+    /// Get a referral by ID
+    pub fn get_referral(env: Env, referral_id: u64) -> Result<Referral, ContractError> {
+        referral::get_referral(&env, referral_id).ok_or(ContractError::ReferralNotFound)
+    }
+
+    /// Get all referral IDs for a patient
+    pub fn get_patient_referrals(env: Env, patient: Address) -> Vec<u64> {
+        referral::get_patient_referrals(&env, &patient)
+    }
+
+    /// Get all referral IDs a provider sent or received
+    pub fn get_provider_referrals(env: Env, provider: Address) -> Vec<u64> {
+        referral::get_provider_referrals(&env, &provider)
+    }
+
+    /// Check the access level `grantee` has on a single record, through either
+    /// a patient-wide grant or a record-scoped grant
+    pub fn check_record_access(env: Env, record_id: u64, grantee: Address) -> AccessLevel {
+        let record = match Self::get_record(env.clone(), record_id) {
+            Ok(record) => record,
+            Err(_) => return AccessLevel::None,
+        };
+
+        let level = Self::check_access(env.clone(), record.patient, grantee.clone());
+        if level != AccessLevel::None {
+            return level;
+        }
+
+        referral::get_record_access(&env, record_id, &grantee)
+    }
+
+    // ======================== Provider Credential Endpoints ========================
+
+    /// Attest a provider's license. Only accredited verifiers (holders of
+    /// `VerifyCredentials`) may call this. Replaces any previous credential.
+    pub fn attest_credential(
+        env: Env,
+        verifier: Address,
+        provider: Address,
+        license_hash: BytesN<32>,
+        jurisdiction: String,
+        specialty: Role,
+        expires_at: u64,
+    ) -> Result<(), ContractError> {
+        verifier.require_auth();
+
+        if !rbac::has_permission(&env, &verifier, &Permission::VerifyCredentials) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if !credentials::is_clinical_role(&specialty)
+            || jurisdiction.is_empty()
+            || expires_at <= env.ledger().timestamp()
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let credential = ProviderCredential {
+            provider: provider.clone(),
+            license_hash,
+            jurisdiction,
+            specialty: specialty.clone(),
+            verifier: verifier.clone(),
+            verified_at: env.ledger().timestamp(),
+            expires_at,
+            status: CredentialStatus::Active,
+        };
+        credentials::set_credential(&env, &credential);
+
+        events::publish_credential_attested(
+            &env,
+            verifier.clone(),
+            provider,
+            verifier,
+            specialty,
+            expires_at,
+        );
+
+        Ok(())
+    }
+
+    /// Suspend a provider's credential, withdrawing their clinical permissions
+    pub fn suspend_credential(
+        env: Env,
+        verifier: Address,
+        provider: Address,
+    ) -> Result<(), ContractError> {
+        set_credential_status(&env, verifier, provider, CredentialStatus::Suspended)
+    }
+
+    /// Reinstate a suspended credential
+    pub fn reinstate_credential(
+        env: Env,
+        verifier: Address,
+        provider: Address,
+    ) -> Result<(), ContractError> {
+        set_credential_status(&env, verifier, provider, CredentialStatus::Active)
+    }
+
+    /// Get a provider's credential
+    pub fn get_credential(
+        env: Env,
+        provider: Address,
+    ) -> Result<ProviderCredential, ContractError> {
+        credentials::get_credential(&env, &provider).ok_or(ContractError::CredentialNotFound)
+    }
+
+    /// Check that a provider holds an active, unexpired credential for `specialty`
+    pub fn has_valid_credential(env: Env, provider: Address, specialty: Role) -> bool {
+        credentials::has_valid_credential(&env, &provider, &specialty)
+    }
+
+    // ======================== Read Audit Endpoints ========================
+
+    /// Read a record on behalf of `caller` for a declared purpose of use,
+    /// recording who read it and why in the patient's access log
+    pub fn access_record(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+        purpose: PurposeOfUse,
+    ) -> Result<VisionRecord, ContractError> {
+        caller.require_auth();
+
+        let record = Self::get_record(env.clone(), record_id)?;
+        if !can_read_record(&env, &caller, &record, &purpose) {
+            return Err(ContractError::AccessDenied);
+        }
+
+        let entry = ReadAuditEntry {
+            record_id,
+            patient: record.patient.clone(),
+            actor: caller.clone(),
+            purpose: purpose.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+        audit::append_entry(&env, &entry);
+
+        events::publish_record_accessed(&env, caller, record_id, record.patient.clone(), purpose);
+
+        Ok(record)
+    }
+
+    /// Page through the reads of a patient's records, oldest first. Only the
+    /// patient and holders of `ReadAuditLog` may view the log.
+    pub fn get_access_log(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        offset: u64,
+        limit: u32,
+    ) -> Result<Vec<ReadAuditEntry>, ContractError> {
+        caller.require_auth();
+        if caller != patient && !rbac::has_permission(&env, &caller, &Permission::ReadAuditLog) {
+            return Err(ContractError::Unauthorized);
+        }
+        Ok(audit::get_entries(&env, &patient, offset, limit))
+    }
+
+    /// Number of entries in a patient's access log, visible to the same
+    /// callers as the log itself
+    pub fn get_access_log_count(
+        env: Env,
+        caller: Address,
+        patient: Address,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+        if caller != patient && !rbac::has_permission(&env, &caller, &Permission::ReadAuditLog) {
+            return Err(ContractError::Unauthorized);
+        }
+        Ok(audit::get_entry_count(&env, &patient))
+    }
+
+    // ======================== Research Endpoints ========================
+
+    /// Register a research study. Requires `ReadResearchData`.
+    pub fn create_study(env: Env, caller: Address, title: String) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::ReadResearchData) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let id = research::next_study_id(&env);
+        let study = Study {
+            id,
+            title,
+            investigator: caller.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+        research::save_study(&env, &study);
+        research::create_salt(&env, id);
+
+        events::publish_study_created(&env, caller, id);
+
+        Ok(id)
+    }
+
+    /// Get a research study by ID
+    pub fn get_study(env: Env, study_id: u64) -> Result<Study, ContractError> {
+        research::get_study(&env, study_id).ok_or(ContractError::StudyNotFound)
+    }
+
+    /// Opt in to sharing record hashes with a study, or with every study when
+    /// `study_id` is 0. An empty `record_types` shares every type. Replaces
+    /// any earlier opt-in for the same study. Fails with `AccessDenied` while
+    /// the patient's consent directive denies research.
+    pub fn opt_in_research(
+        env: Env,
+        patient: Address,
+        study_id: u64,
+        record_types: Vec<RecordType>,
+    ) -> Result<(), ContractError> {
+        patient.require_auth();
+
+        if study_id != research::ALL_STUDIES && research::get_study(&env, study_id).is_none() {
+            return Err(ContractError::StudyNotFound);
+        }
+        if !permits_research(&env, &patient) {
+            return Err(ContractError::AccessDenied);
+        }
+
+        let consent = ResearchConsent {
+            patient: patient.clone(),
+            study_id,
+            record_types,
+            opted_in_at: env.ledger().timestamp(),
+        };
+        research::opt_in(&env, &consent);
+
+        events::publish_research_opt_in(&env, &consent);
+
+        Ok(())
+    }
+
+    /// Withdraw an opt-in. The patient drops out of the affected cohorts at once.
+    pub fn withdraw_research_consent(
+        env: Env,
+        patient: Address,
+        study_id: u64,
+    ) -> Result<(), ContractError> {
+        patient.require_auth();
+
+        if !research::withdraw(&env, &patient, study_id) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        events::publish_research_opt_out(&env, study_id);
+
+        Ok(())
+    }
+
+    /// List the consenting patients of a study under their study pseudonyms,
+    /// with per-study digests of the records they agreed to share. Requires
+    /// `ReadResearchData`. Patients whose consent directive now denies
+    /// research are left out.
+    pub fn get_study_cohort(
+        env: Env,
+        caller: Address,
+        study_id: u64,
+    ) -> Result<Vec<CohortMember>, ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::ReadResearchData) {
+            return Err(ContractError::Unauthorized);
+        }
+        if research::get_study(&env, study_id).is_none() {
+            return Err(ContractError::StudyNotFound);
+        }
+
+        let mut patients = research::get_participants(&env, study_id);
+        for patient in research::get_participants(&env, research::ALL_STUDIES).iter() {
+            if !patients.contains(&patient) {
+                patients.push_back(patient);
+            }
+        }
+
+        let mut cohort = Vec::new(&env);
+        for patient in patients.iter() {
+            if !permits_research(&env, &patient) {
+                continue;
+            }
+            let study_consent = research::get_consent(&env, &patient, study_id);
+            let general_consent = research::get_consent(&env, &patient, research::ALL_STUDIES);
+
+            let mut records = Vec::new(&env);
+            for record_id in Self::get_patient_records(env.clone(), patient.clone()).iter() {
+                let record = match Self::get_record(env.clone(), record_id) {
+                    Ok(record) => record,
+                    Err(_) => continue,
+                };
+                let shared = study_consent
+                    .as_ref()
+                    .is_some_and(|c| research::covers(c, &record.record_type))
+                    || general_consent
+                        .as_ref()
+                        .is_some_and(|c| research::covers(c, &record.record_type));
+                if shared {
+                    records.push_back(CohortRecord {
+                        record_type: record.record_type,
+                        data_digest: research::data_digest(&env, study_id, &record.data_hash),
+                        created_at: record.created_at,
+                    });
+                }
+            }
+
+            if !records.is_empty() {
+                cohort.push_back(CohortMember {
+                    pseudonym: research::pseudonym(&env, study_id, &patient),
+                    records,
+                });
+            }
+        }
+
+        Ok(cohort)
+    }
+
+    // ======================== Attestation Endpoints ========================
+
+    /// Issue an attestation about a patient. The issuer needs `WriteRecord`
+    /// and an active, unexpired credential.
+    pub fn issue_attestation(
+        env: Env,
+        issuer: Address,
+        subject: Address,
+        claim_type: ClaimType,
+        expires_at: u64,
+    ) -> Result<u64, ContractError> {
+        issuer.require_auth();
+
+        if !rbac::has_permission(&env, &issuer, &Permission::WriteRecord)
+            || !credentials::has_active_credential(&env, &issuer)
+        {
+            return Err(ContractError::Unauthorized);
+        }
+        Self::get_user(env.clone(), subject.clone())?;
+        if expires_at <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let attestation = Attestation {
+            id: attestation::next_attestation_id(&env),
+            issuer: issuer.clone(),
+            subject,
+            claim_type,
+            issued_at: env.ledger().timestamp(),
+            expires_at,
+            revoked: false,
+        };
+        attestation::issue(&env, &attestation);
+
+        events::publish_attestation_issued(&env, issuer, &attestation);
+
+        Ok(attestation.id)
+    }
+
+    /// Revoke an attestation. Callable by its issuer or a system admin.
+    pub fn revoke_attestation(
+        env: Env,
+        caller: Address,
+        attestation_id: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut attestation = attestation::get_attestation(&env, attestation_id)
+            .ok_or(ContractError::AttestationNotFound)?;
+        if caller != attestation.issuer
+            && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin)
+        {
+            return Err(ContractError::Unauthorized);
+        }
+        if attestation.revoked {
+            return Err(ContractError::InvalidInput);
+        }
+        attestation.revoked = true;
+        attestation::save_attestation(&env, &attestation);
+
+        events::publish_attestation_revoked(&env, caller, attestation_id, attestation.subject);
+
+        Ok(())
+    }
+
+    /// Get an attestation by ID
+    pub fn get_attestation(env: Env, attestation_id: u64) -> Result<Attestation, ContractError> {
+        attestation::get_attestation(&env, attestation_id).ok_or(ContractError::AttestationNotFound)
+    }
+
+    /// List the attestations issued about a patient
+    pub fn get_subject_attestations(env: Env, subject: Address) -> Vec<u64> {
+        attestation::get_subject_attestations(&env, &subject)
+    }
+
+    /// Check that an attestation makes `claim_type` about `subject` and is
+    /// neither revoked nor expired. Needs no record access.
+    pub fn verify_attestation(
+        env: Env,
+        attestation_id: u64,
+        subject: Address,
+        claim_type: ClaimType,
+    ) -> bool {
+        attestation::get_attestation(&env, attestation_id).is_some_and(|a| {
+            a.subject == subject && a.claim_type == claim_type && attestation::is_active(&env, &a)
+        })
+    }
+
+    /// Check whether `subject` holds any active attestation of `claim_type`
+    pub fn has_valid_attestation(env: Env, subject: Address, claim_type: ClaimType) -> bool {
+        attestation::get_subject_attestations(&env, &subject)
+            .iter()
+            .any(|id| {
+                Self::verify_attestation(env.clone(), id, subject.clone(), claim_type.clone())
+            })
+    }
+
+    // ======================== Organization Endpoints ========================
+
+    /// Create an organization with `admin` as its first administrator.
+    /// Requires `SystemAdmin`.
+    pub fn create_organization(
+        env: Env,
+        caller: Address,
+        name: String,
+        admin: Address,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Err(ContractError::Unauthorized);
+        }
+        if name.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+        Self::get_user(env.clone(), admin.clone())?;
+
+        let org = Organization {
+            id: organization::next_org_id(&env),
+            name: name.clone(),
+            created_by: caller.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+        organization::save_org(&env, &org);
+        events::publish_organization_created(&env, caller.clone(), org.id, name);
+
+        let member = OrgMember {
+            org_id: org.id,
+            member: admin,
+            role: OrgRole::Admin,
+            on_duty: false,
+            joined_at: env.ledger().timestamp(),
+        };
+        organization::set_member(&env, &member);
+        events::publish_org_member_set(&env, caller, &member);
+
+        Ok(org.id)
+    }
+
+    /// Get an organization by ID
+    pub fn get_organization(env: Env, org_id: u64) -> Result<Organization, ContractError> {
+        organization::get_org(&env, org_id).ok_or(ContractError::OrganizationNotFound)
+    }
+
+    /// Add a registered user to an organization, or change a member's role.
+    /// Only the organization's own admins may manage its members. Providers
+    /// must hold a clinical role and a valid credential for it.
+    pub fn add_org_member(
+        env: Env,
+        caller: Address,
+        org_id: u64,
+        member: Address,
+        role: OrgRole,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if organization::get_org(&env, org_id).is_none() {
+            return Err(ContractError::OrganizationNotFound);
+        }
+        if !organization::is_org_admin(&env, org_id, &caller) {
+            return Err(ContractError::Unauthorized);
+        }
+        Self::get_user(env.clone(), member.clone())?;
+        if role == OrgRole::Provider && !organization::is_credentialed_provider(&env, &member) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let existing = organization::get_member(&env, org_id, &member);
+        if role != OrgRole::Admin
+            && existing.as_ref().is_some_and(|m| m.role == OrgRole::Admin)
+            && organization::admin_count(&env, org_id) == 1
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let member = OrgMember {
+            org_id,
+            member,
+            role,
+            on_duty: existing.as_ref().is_some_and(|m| m.on_duty),
+            joined_at: existing.map_or(env.ledger().timestamp(), |m| m.joined_at),
+        };
+        organization::set_member(&env, &member);
+
+        events::publish_org_member_set(&env, caller, &member);
+
+        Ok(())
+    }
+
+    /// Remove a member from an organization. The last admin cannot be removed.
+    pub fn remove_org_member(
+        env: Env,
+        caller: Address,
+        org_id: u64,
+        member: Address,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if organization::get_org(&env, org_id).is_none() {
+            return Err(ContractError::OrganizationNotFound);
+        }
+        if !organization::is_org_admin(&env, org_id, &caller) {
+            return Err(ContractError::Unauthorized);
+        }
+        if organization::is_org_admin(&env, org_id, &member)
+            && organization::admin_count(&env, org_id) == 1
+        {
+            return Err(ContractError::InvalidInput);
+        }
+        if !organization::remove_member(&env, org_id, &member) {
+            return Err(ContractError::UserNotFound);
+        }
+
+        events::publish_org_member_removed(&env, caller, org_id, member);
+
+        Ok(())
+    }
+
+    /// Start or end a member's shift. Callable by the member or an org admin.
+    pub fn set_on_duty(
+        env: Env,
+        caller: Address,
+        org_id: u64,
+        member: Address,
+        on_duty: bool,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if caller != member && !organization::is_org_admin(&env, org_id, &caller) {
+            return Err(ContractError::Unauthorized);
+        }
+        let mut entry =
+            organization::get_member(&env, org_id, &member).ok_or(ContractError::UserNotFound)?;
+        entry.on_duty = on_duty;
+        organization::set_member(&env, &entry);
+
+        events::publish_org_member_set(&env, caller, &entry);
+
+        Ok(())
+    }
+
+    /// Get a user's membership of an organization
+    pub fn get_org_member(
+        env: Env,
+        org_id: u64,
+        member: Address,
+    ) -> Result<OrgMember, ContractError> {
+        organization::get_member(&env, org_id, &member).ok_or(ContractError::UserNotFound)
+    }
+
+    /// List the members of an organization
+    pub fn get_org_members(env: Env, org_id: u64) -> Vec<Address> {
+        organization::get_members(&env, org_id)
+    }
+
+    /// Add a record owned by an organization. `provider` must be one of its
+    /// providers; the organization's on-duty providers can then read the
+    /// record as its own provider can.
+    pub fn add_org_record(
+        env: Env,
+        caller: Address,
+        org_id: u64,
+        patient: Address,
+        provider: Address,
+        record_type: RecordType,
+        data_hash: String,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        if organization::get_org(&env, org_id).is_none() {
+            return Err(ContractError::OrganizationNotFound);
+        }
+        let is_provider = organization::get_member(&env, org_id, &provider)
+            .is_some_and(|m| m.role == OrgRole::Provider);
+        if !is_provider || !can_write_for_provider(&env, &caller, &provider) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let record_id = store_record(
+            &env,
+            caller.clone(),
+            patient,
+            provider,
+            record_type,
+            data_hash,
+        )?;
+        organization::set_record_org(&env, record_id, org_id);
+
+        events::publish_org_record_added(&env, caller, org_id, record_id);
+
+        Ok(record_id)
+    }
+
+    /// Get the organization owning a record, if any
+    pub fn get_record_org(env: Env, record_id: u64) -> Option<u64> {
+        organization::get_record_org(&env, record_id)
+    }
+
+    // ======================== Care Team Endpoints ========================
+
+    /// Create a named care team. Grant it access with `grant_group_access`
+    /// and `GrantTarget::CareTeam`.
+    pub fn create_care_team(
+        env: Env,
+        patient: Address,
+        name: String,
+    ) -> Result<u64, ContractError> {
+        patient.require_auth();
+
+        if name.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let team = CareTeam {
+            id: care_team::next_team_id(&env),
+            patient: patient.clone(),
+            name,
+            members: Vec::new(&env),
+            created_at: env.ledger().timestamp(),
+        };
+        care_team::create_team(&env, &team);
+
+        events::publish_care_team_created(&env, patient, &team);
+
+        Ok(team.id)
+    }
+
+    /// Add a registered user to one of the patient's care teams. They are
+    /// covered by the team's grants at once.
+    pub fn add_care_team_member(
+        env: Env,
+        patient: Address,
+        team_id: u64,
+        member: Address,
+    ) -> Result<(), ContractError> {
+        patient.require_auth();
+
+        let mut team = care_team::get_team(&env, team_id).ok_or(ContractError::CareTeamNotFound)?;
+        if team.patient != patient {
+            return Err(ContractError::Unauthorized);
+        }
+        Self::get_user(env.clone(), member.clone())?;
+        if !care_team::add_member(&env, &mut team, &member) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        events::publish_care_team_member_added(&env, patient, team_id, member);
+
+        Ok(())
+    }
+
+    /// Remove a member from one of the patient's care teams. Their access
+    /// through the team ends at once; if the team holds a grant, their
+    /// envelopes go stale and the patient's key epoch advances.
+    pub fn remove_care_team_member(
+        env: Env,
+        patient: Address,
+        team_id: u64,
+        member: Address,
+    ) -> Result<(), ContractError> {
+        patient.require_auth();
+
+        let mut team = care_team::get_team(&env, team_id).ok_or(ContractError::CareTeamNotFound)?;
+        if team.patient != patient {
+            return Err(ContractError::Unauthorized);
+        }
+        if !care_team::remove_member(&env, &mut team, &member) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        events::publish_care_team_member_removed(&env, patient.clone(), team_id, member.clone());
+
+        // A member leaving a team that holds a grant may have kept content keys
+        let target = GrantTarget::CareTeam(team_id);
+        if groups::get_targets(&env, &patient).contains(&target) {
+            mark_envelopes_stale(&env, patient.clone(), &patient, member);
+            advance_key_epoch(&env, patient.clone(), &patient);
+        }
+
+        Ok(())
+    }
+
+    /// Get a care team by ID
+    pub fn get_care_team(env: Env, team_id: u64) -> Result<CareTeam, ContractError> {
+        care_team::get_team(&env, team_id).ok_or(ContractError::CareTeamNotFound)
+    }
+
+    /// List the care teams a patient has created
+    pub fn get_patient_care_teams(env: Env, patient: Address) -> Vec<u64> {
+        care_team::get_patient_teams(&env, &patient)
+    }
+
+    /// List the care teams a user is a member of
+    pub fn get_member_care_teams(env: Env, member: Address) -> Vec<u64> {
+        care_team::get_member_teams(&env, &member)
+    }
+
+    // ======================== Group Access Endpoints ========================
+
+    /// Grant a group access to the patient's records for the purposes in the
+    /// patient's consent directive. Whoever belongs to the group when a read
+    /// is checked is covered, so membership changes apply at once.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn grant_group_access(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        target: GrantTarget,
+        level: AccessLevel,
+        duration_seconds: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !can_manage_access(&env, &caller, &patient) {
+            return Err(ContractError::Unauthorized);
+        }
+        check_rate_limits(&env, RateLimitedAction::GrantAccess, &caller, &patient)?;
+        match &target {
+            GrantTarget::Organization(_) if !groups::exists(&env, &target) => {
+                return Err(ContractError::OrganizationNotFound);
+            }
+            GrantTarget::CareTeam(team_id) => {
+                let team =
+                    care_team::get_team(&env, *team_id).ok_or(ContractError::CareTeamNotFound)?;
+                // Teams only carry grants from the patient who curates them
+                if team.patient != patient {
+                    return Err(ContractError::InvalidInput);
+                }
+            }
+            _ => {}
+        }
+
+        let grant = GroupGrant {
+            patient: patient.clone(),
+            target,
+            level,
+            purposes: consent::get_directive(&env, &patient).default_purposes,
+            granted_at: env.ledger().timestamp(),
+            expires_at: env.ledger().timestamp() + duration_seconds,
+        };
+        groups::set_grant(&env, &grant);
+
+        events::publish_group_access_granted(&env, caller, &grant);
+
+        Ok(())
+    }
+
+    /// Revoke a group's access to the patient's records. Envelopes held by
+    /// the group's members go stale and the patient's key epoch advances.
+    pub fn revoke_group_access(
+        env: Env,
+        patient: Address,
+        target: GrantTarget,
+    ) -> Result<(), ContractError> {
+        patient.require_auth();
+
+        if !groups::remove_grant(&env, &patient, &target) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        events::publish_group_access_revoked(
+            &env,
+            patient.clone(),
+            patient.clone(),
+            target.clone(),
+        );
+
+        // As with a personal grant, anyone the group grant covered may have
+        // kept content keys
+        for member in groups::members(&env, &target).iter() {
+            mark_envelopes_stale(&env, patient.clone(), &patient, member);
+        }
+        advance_key_epoch(&env, patient.clone(), &patient);
+
+        Ok(())
+    }
+
+    /// Get the level of a group's unexpired grant from the patient
+    pub fn check_group_access(env: Env, patient: Address, target: GrantTarget) -> AccessLevel {
+        match groups::get_active_grant(&env, &patient, &target) {
+            Some(grant) => grant.level,
+            None => AccessLevel::None,
+        }
+    }
+
+    /// List the patient's unexpired group grants
+    pub fn get_group_grants(env: Env, patient: Address) -> Vec<GroupGrant> {
+        let mut grants = Vec::new(&env);
+        for target in groups::get_targets(&env, &patient).iter() {
+            if let Some(grant) = groups::get_active_grant(&env, &patient, &target) {
+                grants.push_back(grant);
+            }
+        }
+        grants
+    }
+
+    // ======================== Key Envelope Endpoints ========================
+
+    /// Register or replace the caller's public encryption key, to which
+    /// content keys are wrapped for them
+    pub fn set_encryption_key(
+        env: Env,
+        user: Address,
+        public_key: Bytes,
+    ) -> Result<(), ContractError> {
+        user.require_auth();
+
+        Self::get_user(env.clone(), user.clone())?;
+        if public_key.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+        keys::set_public_key(&env, &user, &public_key);
+
+        events::publish_encryption_key_set(&env, user.clone(), user);
+
+        Ok(())
+    }
+
+    /// Get a user's public encryption key
+    pub fn get_encryption_key(env: Env, user: Address) -> Option<Bytes> {
+        keys::get_public_key(&env, &user)
+    }
+
+    /// Grant access as `grant_access` does, and store the grantee's key
+    /// envelopes (record ID to wrapped content key) in the same call
+    pub fn grant_access_with_keys(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        grantee: Address,
+        level: AccessLevel,
+        duration_seconds: u64,
+        envelopes: Map<u64, Bytes>,
+    ) -> Result<(), ContractError> {
+        grant_patient_access(
+            &env,
+            caller.clone(),
+            patient.clone(),
+            grantee.clone(),
+            level,
+            duration_seconds,
+            None,
+        )?;
+        store_key_envelopes(&env, caller, patient, grantee, envelopes)
+    }
+
+    /// Store further key envelopes for a grantee who already holds access,
+    /// such as for records added after the grant
+    pub fn put_key_envelopes(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        grantee: Address,
+        envelopes: Map<u64, Bytes>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !can_manage_access(&env, &caller, &patient) {
+            return Err(ContractError::Unauthorized);
+        }
+        if Self::check_access(env.clone(), patient.clone(), grantee.clone()) == AccessLevel::None {
+            return Err(ContractError::AccessDenied);
+        }
+
+        store_key_envelopes(&env, caller, patient, grantee, envelopes)
+    }
+
+    /// Fetch the caller's key envelope for a record. Requires current access
+    /// to the record; stale envelopes and those issued before the record's
+    /// last key rotation are never served.
+    pub fn get_key_envelope(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+    ) -> Result<KeyEnvelope, ContractError> {
+        caller.require_auth();
+
+        Self::get_record(env.clone(), record_id)?;
+        if Self::check_record_access(env.clone(), record_id, caller.clone()) == AccessLevel::None {
+            return Err(ContractError::AccessDenied);
+        }
+
+        let epoch = keys::get_record_epoch(&env, record_id);
+        keys::get_envelope(&env, record_id, &caller)
+            .filter(|envelope| !envelope.stale && envelope.epoch == epoch)
+            .ok_or(ContractError::KeyNotFound)
+    }
+
+    /// Post a record re-encrypted under the patient's current key epoch: its
+    /// new `data_hash` and fresh envelopes (grantee to wrapped key) for those
+    /// who still have access. Callable by whoever may write as the record's
+    /// provider. Envelopes from earlier epochs stop being served. Fails with
+    /// `RecordNotFound` for a record not yet in the patient's Merkle tree.
+    pub fn rotate_record_key(
+        env: Env,
+        caller: Address,
+        record_id: u64,
+        data_hash: String,
+        envelopes: Map<Address, Bytes>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut record = Self::get_record(env.clone(), record_id)?;
+        if !can_write_for_provider(&env, &caller, &record.provider) {
+            return Err(ContractError::Unauthorized);
+        }
+        if data_hash.is_empty() || !keys::get_pending(&env, &record.patient).contains(record_id) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let epoch = keys::get_key_epoch(&env, &record.patient);
+        record.data_hash = data_hash.clone();
+        record.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&(symbol_short!("RECORD"), record_id), &record);
+        // Records from before the tree must be backfilled before rotating
+        merkle::update(&env, &record).ok_or(ContractError::RecordNotFound)?;
+        keys::set_record_epoch(&env, record_id, epoch);
+        keys::clear_pending(&env, &record.patient, record_id);
+
+        events::publish_record_key_rotated(
+            &env,
+            caller.clone(),
+            record.patient.clone(),
+            record_id,
+            epoch,
+            data_hash,
+        );
+
+        for (grantee, wrapped_key) in envelopes.iter() {
+            if Self::check_access(env.clone(), record.patient.clone(), grantee.clone())
+                == AccessLevel::None
+            {
+                return Err(ContractError::AccessDenied);
+            }
+            store_key_envelopes(
+                &env,
+                caller.clone(),
+                record.patient.clone(),
+                grantee,
+                Map::from_array(&env, [(record_id, wrapped_key)]),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the patient's current key epoch
+    pub fn get_key_epoch(env: Env, patient: Address) -> u64 {
+        keys::get_key_epoch(&env, &patient)
+    }
+
+    /// Get the key epoch a record's payload is encrypted under
+    pub fn get_record_key_epoch(env: Env, record_id: u64) -> u64 {
+        keys::get_record_epoch(&env, record_id)
+    }
+
+    /// List the patient's records still awaiting re-encryption
+    pub fn get_pending_rotations(env: Env, patient: Address) -> Vec<u64> {
+        keys::get_pending(&env, &patient)
+    }
+
+    // ======================== Appointment Endpoints ========================
+
+    /// Book a slot between a patient and a provider. Requires
+    /// `ManageAppointments`; the provider must hold `WriteRecord`.
+    pub fn book_appointment(
+        env: Env,
+        caller: Address,
+        patient: Address,
+        provider: Address,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::ManageAppointments) {
+            return Err(ContractError::Unauthorized);
+        }
+        Self::get_user(env.clone(), patient.clone())?;
+        Self::get_user(env.clone(), provider.clone())?;
+        if !rbac::has_permission(&env, &provider, &Permission::WriteRecord)
+            || patient == provider
+            || start_time < env.ledger().timestamp()
+            || end_time <= start_time
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let appointment = Appointment {
+            id: appointment::next_appointment_id(&env),
+            patient,
+            provider,
+            booked_by: caller.clone(),
+            start_time,
+            end_time,
+            status: AppointmentStatus::Booked,
+            grant_expires_at: 0,
+            prior_expires_at: 0,
+            prior_not_before: 0,
+            created_at: env.ledger().timestamp(),
+        };
+        appointment::book(&env, &appointment);
+
+        events::publish_appointment_booked(&env, caller, &appointment);
+
+        Ok(appointment.id)
+    }
+
+    /// Confirm a booked appointment as its patient. The provider gets a
+    /// treatment grant from `ACCESS_LEAD_SECONDS` before the appointment
+    /// starts until `ACCESS_GRACE_SECONDS` after it ends. An existing grant
+    /// keeps its level and purposes and only has its window widened.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn confirm_appointment(
+        env: Env,
+        patient: Address,
+        appointment_id: u64,
+    ) -> Result<(), ContractError> {
+        patient.require_auth();
+
+        let mut appointment = appointment::get_appointment(&env, appointment_id)
+            .ok_or(ContractError::AppointmentNotFound)?;
+        if patient != appointment.patient {
+            return Err(ContractError::Unauthorized);
+        }
+        if appointment.status != AppointmentStatus::Booked
+            || appointment.end_time <= env.ledger().timestamp()
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let now = env.ledger().timestamp();
+        let expires_at = appointment.end_time + appointment::ACCESS_GRACE_SECONDS;
+        let mut not_before = appointment
+            .start_time
+            .saturating_sub(appointment::ACCESS_LEAD_SECONDS);
+        if not_before <= now {
+            not_before = 0;
+        }
+
+        match get_unexpired_grant(&env, &patient, &appointment.provider) {
+            // An existing grant only has its window widened, so its level and
+            // purposes are kept
+            Some(grant) => {
+                let prior_not_before = get_grant_not_before(&env, &patient, &appointment.provider);
+                let new_not_before = prior_not_before.min(not_before);
+                let new_expires_at = grant.expires_at.max(expires_at);
+                if new_not_before != prior_not_before || new_expires_at != grant.expires_at {
+                    appointment.prior_expires_at = grant.expires_at;
+                    appointment.prior_not_before = prior_not_before;
+                    appointment.grant_expires_at = new_expires_at;
+                    set_grant_window(&env, patient.clone(), grant, new_not_before, new_expires_at);
+                }
+            }
+            None => {
+                store_patient_grant(
+                    &env,
+                    patient.clone(),
+                    patient.clone(),
+                    appointment.provider.clone(),
+                    AccessLevel::Read,
+                    expires_at - now,
+                    Some(Vec::from_array(&env, [PurposeOfUse::Treatment])),
+                )?;
+                set_grant_not_before(&env, &patient, &appointment.provider, not_before);
+                appointment.grant_expires_at = expires_at;
+            }
+        }
+        appointment.status = AppointmentStatus::Confirmed;
+        appointment::save_appointment(&env, &appointment);
+
+        events::publish_appointment_status_changed(&env, patient, &appointment);
+
+        Ok(())
+    }
+
+    /// Cancel a booked or confirmed appointment. Callable by its patient, its
+    /// provider or a holder of `ManageAppointments`. Ends the appointment's
+    /// access grant, or restores the grant it extended.
+    pub fn cancel_appointment(
+        env: Env,
+        caller: Address,
+        appointment_id: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut appointment = appointment::get_appointment(&env, appointment_id)
+            .ok_or(ContractError::AppointmentNotFound)?;
+        if caller != appointment.patient
+            && caller != appointment.provider
+            && !rbac::has_permission(&env, &caller, &Permission::ManageAppointments)
+        {
+            return Err(ContractError::Unauthorized);
+        }
+        if appointment.status != AppointmentStatus::Booked
+            && appointment.status != AppointmentStatus::Confirmed
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        end_appointment_grant(&env, caller.clone(), &appointment);
+        appointment.status = AppointmentStatus::Cancelled;
+        appointment::save_appointment(&env, &appointment);
+
+        events::publish_appointment_status_changed(&env, caller, &appointment);
+
+        Ok(())
+    }
+
+    /// Mark a confirmed appointment whose start has passed as a no-show.
+    /// Callable by its provider or a holder of `ManageAppointments`. Ends the
+    /// appointment's access grant like `cancel_appointment`.
+    pub fn mark_no_show(
+        env: Env,
+        caller: Address,
+        appointment_id: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut appointment = appointment::get_appointment(&env, appointment_id)
+            .ok_or(ContractError::AppointmentNotFound)?;
+        if caller != appointment.provider
+            && !rbac::has_permission(&env, &caller, &Permission::ManageAppointments)
+        {
+            return Err(ContractError::Unauthorized);
+        }
+        if appointment.status != AppointmentStatus::Confirmed
+            || appointment.start_time > env.ledger().timestamp()
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        end_appointment_grant(&env, caller.clone(), &appointment);
+        appointment.status = AppointmentStatus::NoShow;
+        appointment::save_appointment(&env, &appointment);
+
+        events::publish_appointment_status_changed(&env, caller, &appointment);
+
+        Ok(())
+    }
+
+    /// Get an appointment by ID
+    pub fn get_appointment(env: Env, appointment_id: u64) -> Result<Appointment, ContractError> {
+        appointment::get_appointment(&env, appointment_id).ok_or(ContractError::AppointmentNotFound)
+    }
+
+    /// List a patient's appointments, oldest booking first
+    pub fn get_patient_appointments(env: Env, patient: Address) -> Vec<u64> {
+        appointment::get_patient_appointments(&env, &patient)
+    }
+
+    /// List a provider's appointments, oldest booking first
+    pub fn get_provider_appointments(env: Env, provider: Address) -> Vec<u64> {
+        appointment::get_provider_appointments(&env, &provider)
+    }
+
+    // ======================== Claim Endpoints ========================
+
+    /// Submit an insurance claim for services documented in the patient's
+    /// records. Each line must carry its record's current Merkle leaf hash.
+    /// The insurer needs `VerifyRecordExistence`; it gets no record access.
+    pub fn submit_claim(
+        env: Env,
+        caller: Address,
+        provider: Address,
+        patient: Address,
+        insurer: Address,
+        lines: Vec<ClaimLine>,
+        amount: i128,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        if !can_write_for_provider(&env, &caller, &provider) {
+            return Err(ContractError::Unauthorized);
+        }
+        if !rbac::has_permission(&env, &insurer, &Permission::VerifyRecordExistence)
+            || lines.is_empty()
+            || amount <= 0
+        {
+            return Err(ContractError::InvalidInput);
+        }
+        let mut record_ids = Vec::new(&env);
+        for line in lines.iter() {
+            if line.procedure_code.is_empty() || record_ids.contains(line.record_id) {
+                return Err(ContractError::InvalidInput);
+            }
+            record_ids.push_back(line.record_id);
+        }
+
+        let claim = Claim {
+            id: claim::next_claim_id(&env),
+            provider,
+            patient,
+            insurer,
+            submitted_by: caller.clone(),
+            lines,
+            amount,
+            status: ClaimStatus::Submitted,
+            submitted_at: env.ledger().timestamp(),
+            decided_at: 0,
+        };
+        if !claim_matches_records(&env, &claim) {
+            return Err(ContractError::InvalidInput);
+        }
+        claim::submit(&env, &claim);
+
+        events::publish_claim_submitted(&env, caller, &claim);
+
+        Ok(claim.id)
+    }
+
+    /// Check, as the claim's insurer, that every referenced record still
+    /// exists and matches the hash it was claimed with
+    pub fn verify_claim(env: Env, insurer: Address, claim_id: u64) -> Result<bool, ContractError> {
+        insurer.require_auth();
+
+        let claim = claim::get_claim(&env, claim_id).ok_or(ContractError::ClaimNotFound)?;
+        if insurer != claim.insurer {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Ok(claim_matches_records(&env, &claim))
+    }
+
+    /// Approve a submitted claim as its insurer. Fails if the claim no longer
+    /// verifies.
+    pub fn approve_claim(env: Env, insurer: Address, claim_id: u64) -> Result<(), ContractError> {
+        insurer.require_auth();
+
+        let mut claim = claim::get_claim(&env, claim_id).ok_or(ContractError::ClaimNotFound)?;
+        if insurer != claim.insurer {
+            return Err(ContractError::Unauthorized);
+        }
+        if claim.status != ClaimStatus::Submitted || !claim_matches_records(&env, &claim) {
+            return Err(ContractError::InvalidInput);
+        }
+        claim.status = ClaimStatus::Approved;
+        claim.decided_at = env.ledger().timestamp();
+        claim::save_claim(&env, &claim);
+
+        events::publish_claim_decided(&env, insurer, &claim);
+
+        Ok(())
+    }
+
+    /// Deny a submitted claim as its insurer
+    pub fn deny_claim(env: Env, insurer: Address, claim_id: u64) -> Result<(), ContractError> {
+        insurer.require_auth();
+
+        let mut claim = claim::get_claim(&env, claim_id).ok_or(ContractError::ClaimNotFound)?;
+        if insurer != claim.insurer {
+            return Err(ContractError::Unauthorized);
+        }
+        if claim.status != ClaimStatus::Submitted {
+            return Err(ContractError::InvalidInput);
+        }
+        claim.status = ClaimStatus::Denied;
+        claim.decided_at = env.ledger().timestamp();
+        claim::save_claim(&env, &claim);
+
+        events::publish_claim_decided(&env, insurer, &claim);
+
+        Ok(())
+    }
+
+    /// Get a claim. Its procedure codes are sensitive, so only the claim's
+    /// parties and system admins may read it.
+    pub fn get_claim(env: Env, caller: Address, claim_id: u64) -> Result<Claim, ContractError> {
+        caller.require_auth();
+
+        let claim = claim::get_claim(&env, claim_id).ok_or(ContractError::ClaimNotFound)?;
+        if caller != claim.provider
+            && caller != claim.patient
+            && caller != claim.insurer
+            && caller != claim.submitted_by
+            && !rbac::has_permission(&env, &caller, &Permission::SystemAdmin)
+        {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Ok(claim)
+    }
+
+    /// List the claims submitted for a provider
+    pub fn get_provider_claims(env: Env, provider: Address) -> Vec<u64> {
+        claim::get_claims(&env, claim::provider_key(&provider))
+    }
+
+    /// List the claims addressed to an insurer
+    pub fn get_insurer_claims(env: Env, insurer: Address) -> Vec<u64> {
+        claim::get_claims(&env, claim::insurer_key(&insurer))
+    }
+
+    /// List the claims made for a patient's care
+    pub fn get_patient_claims(env: Env, patient: Address) -> Vec<u64> {
+        claim::get_claims(&env, claim::patient_key(&patient))
+    }
+
+    // ======================== Escrow Endpoints ========================
+
+    /// Escrow a payment for a service. The patient's tokens move into the
+    /// contract and become payable to the provider once they add a record of
+    /// `record_type` for the patient within `timeout_seconds`, which may be
+    /// at most `escrow::MAX_TIMEOUT_SECONDS`.
+    pub fn create_escrow(
+        env: Env,
+        patient: Address,
+        provider: Address,
+        token: Address,
+        amount: i128,
+        record_type: RecordType,
+        timeout_seconds: u64,
+    ) -> Result<u64, ContractError> {
+        patient.require_auth();
+
+        Self::get_user(env.clone(), patient.clone())?;
+        if !rbac::has_permission(&env, &provider, &Permission::WriteRecord)
+            || patient == provider
+            || amount <= 0
+            || timeout_seconds == 0
+            || timeout_seconds > escrow::MAX_TIMEOUT_SECONDS
+        {
+            return Err(ContractError::InvalidInput);
+        }
+        let deadline = env
+            .ledger()
+            .timestamp()
+            .checked_add(timeout_seconds)
+            .ok_or(ContractError::InvalidInput)?;
+
+        let escrow = Escrow {
+            id: escrow::next_escrow_id(&env),
+            patient: patient.clone(),
+            provider,
+            token,
+            amount,
+            record_type,
+            deadline,
+            status: EscrowStatus::Held,
+            record_id: 0,
+            created_at: env.ledger().timestamp(),
+        };
+        escrow::open(&env, &escrow);
+
+        events::publish_escrow_created(&env, patient, &escrow);
+
+        Ok(escrow.id)
+    }
+
+    /// Pay a matched escrow out to its provider. Anyone may trigger the
+    /// payout.
+    pub fn release_escrow(env: Env, caller: Address, escrow_id: u64) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut escrow =
+            escrow::get_escrow(&env, escrow_id).ok_or(ContractError::EscrowNotFound)?;
+        if escrow.status != EscrowStatus::Matched {
+            return Err(ContractError::InvalidInput);
+        }
+        let provider = escrow.provider.clone();
+        escrow::settle(&env, &mut escrow, &provider, EscrowStatus::Released);
+
+        events::publish_escrow_settled(&env, caller, &escrow);
+
+        Ok(())
+    }
+
+    /// Return a held payment to the patient once its deadline has passed.
+    /// Anyone may trigger the refund.
+    pub fn refund_escrow(env: Env, caller: Address, escrow_id: u64) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut escrow =
+            escrow::get_escrow(&env, escrow_id).ok_or(ContractError::EscrowNotFound)?;
+        if escrow.status != EscrowStatus::Held || escrow.deadline > env.ledger().timestamp() {
+            return Err(ContractError::InvalidInput);
+        }
+        let patient = escrow.patient.clone();
+        escrow::settle(&env, &mut escrow, &patient, EscrowStatus::Refunded);
+
+        events::publish_escrow_settled(&env, caller, &escrow);
+
+        Ok(())
+    }
+
+    /// Get an escrow by ID
+    pub fn get_escrow(env: Env, escrow_id: u64) -> Result<Escrow, ContractError> {
+        escrow::get_escrow(&env, escrow_id).ok_or(ContractError::EscrowNotFound)
+    }
+
+    /// List the escrows a patient has funded
+    pub fn get_patient_escrows(env: Env, patient: Address) -> Vec<u64> {
+        escrow::get_escrows(&env, escrow::patient_key(&patient))
+    }
+
+    /// List the escrows payable to a provider
+    pub fn get_provider_escrows(env: Env, provider: Address) -> Vec<u64> {
+        escrow::get_escrows(&env, escrow::provider_key(&provider))
+    }
+
+    // ======================== Rate Limit Endpoints ========================
+
+    /// Allow at most `max_calls` calls to `action` per `window_seconds` in
+    /// one scope, or remove the limit with `max_calls` 0. Requires
+    /// `SystemAdmin`. No limits apply until one is set.
+    pub fn set_rate_limit(
+        env: Env,
+        caller: Address,
+        action: RateLimitedAction,
+        scope: RateLimitScope,
+        max_calls: u32,
+        window_seconds: u64,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !rbac::has_permission(&env, &caller, &Permission::SystemAdmin) {
+            return Err(ContractError::Unauthorized);
+        }
+        if max_calls == 0 {
+            rate_limit::remove_limit(&env, &action, &scope);
+        } else if window_seconds == 0 {
+            return Err(ContractError::InvalidInput);
+        } else {
+            let limit = RateLimit {
+                max_calls,
+                window_seconds,
+            };
+            rate_limit::set_limit(&env, &action, &scope, &limit);
+        }
+
+        events::publish_rate_limit_set(&env, caller, action, scope, max_calls, window_seconds);
+
+        Ok(())
+    }
+
+    /// Get the limit on `action` for one scope, if any
+    pub fn get_rate_limit(
+        env: Env,
+        action: RateLimitedAction,
+        scope: RateLimitScope,
+    ) -> Option<RateLimit> {
+        rate_limit::get_limit(&env, &action, &scope)
     }
 }
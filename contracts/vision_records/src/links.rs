@@ -0,0 +1,213 @@
+use crate::RecordType;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const EPI_CTR: Symbol = symbol_short!("EPI_CTR");
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// Clinical relationship between two records.
+///
+/// A link always points from the later record (`source`) to the record it
+/// builds on (`target`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LinkType {
+    /// A `Prescription` derived from an `Examination`
+    DerivedFrom,
+    /// A `Treatment` that follows a `Diagnosis`
+    Follows,
+    /// A follow-up `Examination` after a `Surgery`
+    FollowUpOf,
+}
+
+/// A directed, typed link between two records of the same patient
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordLink {
+    pub source_id: u64,
+    pub target_id: u64,
+    pub link_type: LinkType,
+    pub created_by: Address,
+    pub created_at: u64,
+}
+
+/// The connected set of records reachable from a record through its links
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LinkedGraph {
+    pub record_ids: Vec<u64>,
+    pub links: Vec<RecordLink>,
+}
+
+/// An episode of care grouping related records of one patient
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Episode {
+    pub id: u64,
+    pub patient: Address,
+    pub title: String,
+    pub created_by: Address,
+    pub created_at: u64,
+    pub record_ids: Vec<u64>,
+}
+
+/// Internal store schema helpers
+pub fn links_key(record_id: u64) -> (Symbol, u64) {
+    (symbol_short!("LINKS"), record_id)
+}
+
+pub fn episode_key(episode_id: u64) -> (Symbol, u64) {
+    (symbol_short!("EPISODE"), episode_id)
+}
+
+pub fn record_episode_key(record_id: u64) -> (Symbol, u64) {
+    (symbol_short!("REC_EPI"), record_id)
+}
+
+pub fn patient_episodes_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("PAT_EPI"), patient.clone())
+}
+
+// ======================== Link Rules ========================
+
+/// Returns true if `link_type` is allowed between a `source` and `target`
+/// record of the given types.
+pub fn is_valid_link(source: &RecordType, target: &RecordType, link_type: &LinkType) -> bool {
+    match link_type {
+        LinkType::DerivedFrom => {
+            *source == RecordType::Prescription && *target == RecordType::Examination
+        }
+        LinkType::Follows => *source == RecordType::Treatment && *target == RecordType::Diagnosis,
+        LinkType::FollowUpOf => {
+            *source == RecordType::Examination && *target == RecordType::Surgery
+        }
+    }
+}
+
+// ======================== Link Storage ========================
+
+pub fn get_links(env: &Env, record_id: u64) -> Vec<RecordLink> {
+    env.storage()
+        .persistent()
+        .get(&links_key(record_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Store `link` on both of its endpoints. Returns false if an identical link
+/// between the two records already exists.
+pub fn add_link(env: &Env, link: &RecordLink) -> bool {
+    let mut source_links = get_links(env, link.source_id);
+    for existing in source_links.iter() {
+        if existing.target_id == link.target_id && existing.link_type == link.link_type {
+            return false;
+        }
+    }
+    source_links.push_back(link.clone());
+    env.storage()
+        .persistent()
+        .set(&links_key(link.source_id), &source_links);
+
+    let mut target_links = get_links(env, link.target_id);
+    target_links.push_back(link.clone());
+    env.storage()
+        .persistent()
+        .set(&links_key(link.target_id), &target_links);
+
+    true
+}
+
+/// Walk the links outward from `record_id` and collect every reachable record
+/// and link.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn get_linked_graph(env: &Env, record_id: u64) -> LinkedGraph {
+    let mut record_ids = Vec::new(env);
+    let mut links: Vec<RecordLink> = Vec::new(env);
+    record_ids.push_back(record_id);
+
+    let mut cursor = 0;
+    while cursor < record_ids.len() {
+        let current = record_ids.get_unchecked(cursor);
+        for link in get_links(env, current).iter() {
+            if !links.contains(&link) {
+                links.push_back(link.clone());
+            }
+            let next = if link.source_id == current {
+                link.target_id
+            } else {
+                link.source_id
+            };
+            if !record_ids.contains(next) {
+                record_ids.push_back(next);
+            }
+        }
+        cursor += 1;
+    }
+
+    LinkedGraph { record_ids, links }
+}
+
+// ======================== Episodes ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn create_episode(env: &Env, patient: Address, title: String, created_by: Address) -> u64 {
+    let episode_id: u64 = env.storage().instance().get(&EPI_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&EPI_CTR, &episode_id);
+
+    let episode = Episode {
+        id: episode_id,
+        patient: patient.clone(),
+        title,
+        created_by,
+        created_at: env.ledger().timestamp(),
+        record_ids: Vec::new(env),
+    };
+    env.storage()
+        .persistent()
+        .set(&episode_key(episode_id), &episode);
+
+    let mut patient_episodes: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&patient_episodes_key(&patient))
+        .unwrap_or(Vec::new(env));
+    patient_episodes.push_back(episode_id);
+    env.storage()
+        .persistent()
+        .set(&patient_episodes_key(&patient), &patient_episodes);
+
+    episode_id
+}
+
+pub fn get_episode(env: &Env, episode_id: u64) -> Option<Episode> {
+    env.storage().persistent().get(&episode_key(episode_id))
+}
+
+pub fn get_record_episode(env: &Env, record_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&record_episode_key(record_id))
+}
+
+/// Attach `record_id` to `episode`. A record belongs to at most one episode.
+pub fn add_record_to_episode(env: &Env, mut episode: Episode, record_id: u64) -> bool {
+    if get_record_episode(env, record_id).is_some() {
+        return false;
+    }
+
+    episode.record_ids.push_back(record_id);
+    env.storage()
+        .persistent()
+        .set(&episode_key(episode.id), &episode);
+    env.storage()
+        .persistent()
+        .set(&record_episode_key(record_id), &episode.id);
+    true
+}
+
+pub fn get_patient_episodes(env: &Env, patient: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&patient_episodes_key(patient))
+        .unwrap_or(Vec::new(env))
+}
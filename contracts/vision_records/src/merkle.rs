@@ -0,0 +1,205 @@
+use crate::{RecordType, VisionRecord};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, String, Symbol, Vec};
+
+/// Leaf and node hashes are domain separated, so an inner node can never be
+/// passed off as a leaf
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// The committed fields of a record
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RecordLeaf {
+    pub record_id: u64,
+    pub data_hash: String,
+    pub record_type: RecordType,
+    pub created_at: u64,
+}
+
+/// Sibling hashes from a leaf up to the root, lowest level first
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MerkleProof {
+    pub leaf_index: u32,
+    pub siblings: Vec<BytesN<32>>,
+}
+
+/// Internal store schema helpers
+pub fn size_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("MK_SIZE"), patient.clone())
+}
+
+pub fn node_key(patient: &Address, level: u32, index: u32) -> (Symbol, Address, u32, u32) {
+    (symbol_short!("MK_NODE"), patient.clone(), level, index)
+}
+
+/// Position of a record's leaf in its patient's tree
+pub fn leaf_index_key(record_id: u64) -> (Symbol, u64) {
+    (symbol_short!("MK_IDX"), record_id)
+}
+
+// ======================== Hashing ========================
+
+/// sha256(0x00 ‖ XDR((record_id, data_hash, record_type, created_at)))
+pub fn leaf_hash(env: &Env, leaf: &RecordLeaf) -> BytesN<32> {
+    let fields = (
+        leaf.record_id,
+        leaf.data_hash.clone(),
+        leaf.record_type.clone(),
+        leaf.created_at,
+    );
+    let mut preimage = Bytes::from_array(env, &[LEAF_PREFIX]);
+    preimage.append(&fields.to_xdr(env));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// sha256(0x01 ‖ left ‖ right)
+pub fn node_hash(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &[NODE_PREFIX]);
+    preimage.append(&Bytes::from_array(env, &left.to_array()));
+    preimage.append(&Bytes::from_array(env, &right.to_array()));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Hash of an empty subtree of the given height
+pub fn zero_hash(env: &Env, level: u32) -> BytesN<32> {
+    let mut hash = BytesN::from_array(env, &[0; 32]);
+    for _ in 0..level {
+        hash = node_hash(env, &hash, &hash);
+    }
+    hash
+}
+
+/// Height of a tree holding `size` leaves
+pub fn depth(size: u32) -> u32 {
+    let mut depth = 0;
+    while depth < 32 && (1u64 << depth) < u64::from(size) {
+        depth += 1;
+    }
+    depth
+}
+
+pub fn leaf_of(record: &VisionRecord) -> RecordLeaf {
+    RecordLeaf {
+        record_id: record.id,
+        data_hash: record.data_hash.clone(),
+        record_type: record.record_type.clone(),
+        created_at: record.created_at,
+    }
+}
+
+// ======================== Tree ========================
+
+pub fn get_size(env: &Env, patient: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&size_key(patient))
+        .unwrap_or(0)
+}
+
+pub fn get_leaf_index(env: &Env, record_id: u64) -> Option<u32> {
+    env.storage().persistent().get(&leaf_index_key(record_id))
+}
+
+fn get_node(env: &Env, patient: &Address, level: u32, index: u32) -> BytesN<32> {
+    env.storage()
+        .persistent()
+        .get(&node_key(patient, level, index))
+        .unwrap_or_else(|| zero_hash(env, level))
+}
+
+/// The patient's current root; the zero hash while they have no records
+pub fn get_root(env: &Env, patient: &Address) -> BytesN<32> {
+    let size = get_size(env, patient);
+    if size == 0 {
+        return zero_hash(env, 0);
+    }
+    get_node(env, patient, depth(size), 0)
+}
+
+/// Append a record's leaf to its patient's tree. Returns the new root.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn append(env: &Env, record: &VisionRecord) -> BytesN<32> {
+    let index = get_size(env, &record.patient);
+    env.storage()
+        .persistent()
+        .set(&size_key(&record.patient), &(index + 1));
+    env.storage()
+        .persistent()
+        .set(&leaf_index_key(record.id), &index);
+    set_leaf(
+        env,
+        &record.patient,
+        index,
+        &leaf_hash(env, &leaf_of(record)),
+    )
+}
+
+/// Recommit an amended record in place. Returns the new root.
+pub fn update(env: &Env, record: &VisionRecord) -> Option<BytesN<32>> {
+    let index = get_leaf_index(env, record.id)?;
+    Some(set_leaf(
+        env,
+        &record.patient,
+        index,
+        &leaf_hash(env, &leaf_of(record)),
+    ))
+}
+
+/// Write a leaf and rehash its path up to the root
+#[allow(clippy::arithmetic_side_effects)]
+fn set_leaf(env: &Env, patient: &Address, index: u32, leaf: &BytesN<32>) -> BytesN<32> {
+    let height = depth(get_size(env, patient));
+    let mut hash = leaf.clone();
+    let mut index = index;
+    env.storage()
+        .persistent()
+        .set(&node_key(patient, 0, index), &hash);
+
+    for level in 0..height {
+        hash = if index % 2 == 0 {
+            node_hash(env, &hash, &get_node(env, patient, level, index + 1))
+        } else {
+            node_hash(env, &get_node(env, patient, level, index - 1), &hash)
+        };
+        index /= 2;
+        env.storage()
+            .persistent()
+            .set(&node_key(patient, level + 1, index), &hash);
+    }
+    hash
+}
+
+/// Inclusion proof for the leaf at `index`
+#[allow(clippy::arithmetic_side_effects)]
+pub fn proof(env: &Env, patient: &Address, index: u32) -> MerkleProof {
+    let mut siblings = Vec::new(env);
+    let mut position = index;
+    for level in 0..depth(get_size(env, patient)) {
+        siblings.push_back(get_node(env, patient, level, position ^ 1));
+        position /= 2;
+    }
+    MerkleProof {
+        leaf_index: index,
+        siblings,
+    }
+}
+
+/// Fold a leaf up through its proof
+#[allow(clippy::arithmetic_side_effects)]
+pub fn compute_root(env: &Env, leaf: &RecordLeaf, proof: &MerkleProof) -> BytesN<32> {
+    let mut hash = leaf_hash(env, leaf);
+    let mut index = proof.leaf_index;
+    for sibling in proof.siblings.iter() {
+        hash = if index % 2 == 0 {
+            node_hash(env, &hash, &sibling)
+        } else {
+            node_hash(env, &sibling, &hash)
+        };
+        index /= 2;
+    }
+    hash
+}
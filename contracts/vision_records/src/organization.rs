@@ -0,0 +1,162 @@
+use crate::{credentials, rbac};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const ORG_CTR: Symbol = symbol_short!("ORG_CTR");
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// A member's position within an organization
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OrgRole {
+    /// Manages the organization's membership
+    Admin,
+    /// Counts as a member for the organization's grants while on duty
+    Provider,
+    Staff,
+}
+
+/// A clinic or practice sharing one contract instance with others
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Organization {
+    pub id: u64,
+    pub name: String,
+    pub created_by: Address,
+    pub created_at: u64,
+}
+
+/// A user's membership of an organization
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OrgMember {
+    pub org_id: u64,
+    pub member: Address,
+    pub role: OrgRole,
+    pub on_duty: bool,
+    pub joined_at: u64,
+}
+
+/// Internal store schema helpers
+pub fn org_key(org_id: u64) -> (Symbol, u64) {
+    (symbol_short!("ORG"), org_id)
+}
+
+pub fn member_key(org_id: u64, member: &Address) -> (Symbol, u64, Address) {
+    (symbol_short!("ORG_MEM"), org_id, member.clone())
+}
+
+pub fn members_key(org_id: u64) -> (Symbol, u64) {
+    (symbol_short!("ORG_MEMS"), org_id)
+}
+
+pub fn record_org_key(record_id: u64) -> (Symbol, u64) {
+    (symbol_short!("REC_ORG"), record_id)
+}
+
+// ======================== Organizations ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_org_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&ORG_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&ORG_CTR, &id);
+    id
+}
+
+pub fn get_org(env: &Env, org_id: u64) -> Option<Organization> {
+    env.storage().persistent().get(&org_key(org_id))
+}
+
+pub fn save_org(env: &Env, org: &Organization) {
+    env.storage().persistent().set(&org_key(org.id), org);
+}
+
+// ======================== Membership ========================
+
+pub fn get_member(env: &Env, org_id: u64, member: &Address) -> Option<OrgMember> {
+    env.storage().persistent().get(&member_key(org_id, member))
+}
+
+pub fn get_members(env: &Env, org_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&members_key(org_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add a member, or replace an existing member's role and duty status
+pub fn set_member(env: &Env, member: &OrgMember) {
+    env.storage()
+        .persistent()
+        .set(&member_key(member.org_id, &member.member), member);
+
+    let mut members = get_members(env, member.org_id);
+    if !members.contains(&member.member) {
+        members.push_back(member.member.clone());
+        env.storage()
+            .persistent()
+            .set(&members_key(member.org_id), &members);
+    }
+}
+
+/// Remove a member. Returns false if they were not one.
+pub fn remove_member(env: &Env, org_id: u64, member: &Address) -> bool {
+    let key = member_key(org_id, member);
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+    env.storage().persistent().remove(&key);
+
+    let mut members = get_members(env, org_id);
+    if let Some(index) = members.first_index_of(member) {
+        members.remove(index);
+        env.storage()
+            .persistent()
+            .set(&members_key(org_id), &members);
+    }
+    true
+}
+
+pub fn is_org_admin(env: &Env, org_id: u64, user: &Address) -> bool {
+    get_member(env, org_id, user).is_some_and(|m| m.role == OrgRole::Admin)
+}
+
+/// Whether `user` holds a clinical role backed by a valid credential for it,
+/// as `OrgRole::Provider` requires
+pub fn is_credentialed_provider(env: &Env, user: &Address) -> bool {
+    rbac::get_active_roles(env, user).iter().any(|role| {
+        credentials::is_clinical_role(&role) && credentials::has_valid_credential(env, user, &role)
+    })
+}
+
+/// Whether `user` is a provider of the organization, currently on duty and
+/// still credentialed
+pub fn is_on_duty_provider(env: &Env, org_id: u64, user: &Address) -> bool {
+    get_member(env, org_id, user).is_some_and(|m| m.role == OrgRole::Provider && m.on_duty)
+        && is_credentialed_provider(env, user)
+}
+
+/// Number of members holding `OrgRole::Admin`
+pub fn admin_count(env: &Env, org_id: u64) -> u32 {
+    let mut count = 0u32;
+    for member in get_members(env, org_id).iter() {
+        if is_org_admin(env, org_id, &member) {
+            count = count.saturating_add(1);
+        }
+    }
+    count
+}
+
+// ======================== Records ========================
+
+pub fn set_record_org(env: &Env, record_id: u64, org_id: u64) {
+    env.storage()
+        .persistent()
+        .set(&record_org_key(record_id), &org_id);
+}
+
+/// The organization owning a record, if it was added on behalf of one
+pub fn get_record_org(env: &Env, record_id: u64) -> Option<u64> {
+    env.storage().persistent().get(&record_org_key(record_id))
+}
@@ -0,0 +1,131 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const RX_CTR: Symbol = symbol_short!("RX_CTR");
+
+// ── Types ─────────────────────────────────────────────────────
+//
+// Optical values are stored as fixed-point integers:
+// - powers (sphere, cylinder, add, prism) in hundredths of a diopter
+// - axis in whole degrees (0-180)
+// - pupillary distance, base curve and diameter in hundredths of a millimetre
+
+/// Kind of corrective lens the prescription is written for
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LensType {
+    Spectacle,
+    ContactLens,
+}
+
+/// Direction of the prism base
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PrismBase {
+    None,
+    Up,
+    Down,
+    In,
+    Out,
+}
+
+/// Refraction for a single eye
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EyeRx {
+    pub sphere: i32,
+    pub cylinder: i32,
+    pub axis: u32,
+    pub add: i32,
+    pub prism: u32,
+    pub prism_base: PrismBase,
+    /// Monocular pupillary distance
+    pub pd: u32,
+    /// Contact lens fit; zero/empty for `LensType::Spectacle` prescriptions
+    pub base_curve: u32,
+    pub diameter: u32,
+    pub brand: String,
+}
+
+/// Structured Rx metadata attached to a `RecordType::Prescription` record
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Prescription {
+    pub id: u64,
+    pub record_id: u64,
+    pub patient: Address,
+    pub provider: Address,
+    pub lens_type: LensType,
+    pub left_eye: EyeRx,
+    pub right_eye: EyeRx,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
+
+/// Internal store schema helpers
+pub fn prescription_key(prescription_id: u64) -> (Symbol, u64) {
+    (symbol_short!("RX"), prescription_id)
+}
+
+pub fn patient_prescriptions_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("PAT_RX"), patient.clone())
+}
+
+// ======================== Validation ========================
+
+/// Basic plausibility checks on a single eye's values
+pub fn is_valid_eye_rx(eye: &EyeRx, lens_type: &LensType) -> bool {
+    if eye.axis > 180 || eye.add < 0 {
+        return false;
+    }
+
+    if eye.prism == 0 && eye.prism_base != PrismBase::None {
+        return false;
+    }
+
+    match lens_type {
+        LensType::Spectacle => eye.base_curve == 0 && eye.diameter == 0 && eye.brand.is_empty(),
+        LensType::ContactLens => eye.base_curve > 0 && eye.diameter > 0,
+    }
+}
+
+// ======================== Storage ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_prescription_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&RX_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&RX_CTR, &id);
+    id
+}
+
+pub fn save_prescription(env: &Env, rx: &Prescription) {
+    env.storage().persistent().set(&prescription_key(rx.id), rx);
+
+    let mut patient_rx: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&patient_prescriptions_key(&rx.patient))
+        .unwrap_or(Vec::new(env));
+    patient_rx.push_back(rx.id);
+    env.storage()
+        .persistent()
+        .set(&patient_prescriptions_key(&rx.patient), &patient_rx);
+}
+
+pub fn get_prescription(env: &Env, prescription_id: u64) -> Option<Prescription> {
+    env.storage()
+        .persistent()
+        .get(&prescription_key(prescription_id))
+}
+
+pub fn get_patient_prescriptions(env: &Env, patient: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&patient_prescriptions_key(patient))
+        .unwrap_or(Vec::new(env))
+}
+
+/// An Rx is valid once issued and until (but not including) its expiry
+pub fn is_unexpired(env: &Env, rx: &Prescription) -> bool {
+    rx.expires_at > env.ledger().timestamp()
+}
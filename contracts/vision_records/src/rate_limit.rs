@@ -0,0 +1,111 @@
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// An operation that can be rate limited
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RateLimitedAction {
+    AddRecord,
+    RegisterUser,
+    GrantAccess,
+}
+
+/// Who a limit is counted against
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RateLimitScope {
+    /// The address that authorized the call
+    Caller,
+    /// The patient the call writes to; for registration, the registered user
+    Patient,
+}
+
+/// At most `max_calls` per `window_seconds`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimit {
+    pub max_calls: u32,
+    pub window_seconds: u64,
+}
+
+/// Calls counted in the current fixed window
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RateWindow {
+    pub started_at: u64,
+    pub count: u32,
+}
+
+/// Internal store schema helpers
+pub fn limit_key(
+    action: &RateLimitedAction,
+    scope: &RateLimitScope,
+) -> (Symbol, RateLimitedAction, RateLimitScope) {
+    (symbol_short!("RL_CFG"), action.clone(), scope.clone())
+}
+
+pub fn window_key(
+    action: &RateLimitedAction,
+    scope: &RateLimitScope,
+    address: &Address,
+) -> (Symbol, RateLimitedAction, RateLimitScope, Address) {
+    (
+        symbol_short!("RL_WIN"),
+        action.clone(),
+        scope.clone(),
+        address.clone(),
+    )
+}
+
+// ======================== Limits ========================
+
+pub fn get_limit(
+    env: &Env,
+    action: &RateLimitedAction,
+    scope: &RateLimitScope,
+) -> Option<RateLimit> {
+    env.storage().persistent().get(&limit_key(action, scope))
+}
+
+pub fn set_limit(env: &Env, action: &RateLimitedAction, scope: &RateLimitScope, limit: &RateLimit) {
+    env.storage()
+        .persistent()
+        .set(&limit_key(action, scope), limit);
+}
+
+pub fn remove_limit(env: &Env, action: &RateLimitedAction, scope: &RateLimitScope) {
+    env.storage().persistent().remove(&limit_key(action, scope));
+}
+
+/// Count a call against `address`. Returns false if it would exceed the
+/// configured limit; always true while no limit is configured.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn consume(
+    env: &Env,
+    action: &RateLimitedAction,
+    scope: &RateLimitScope,
+    address: &Address,
+) -> bool {
+    let Some(limit) = get_limit(env, action, scope) else {
+        return true;
+    };
+
+    let now = env.ledger().timestamp();
+    let key = window_key(action, scope, address);
+    let mut window = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .filter(|window: &RateWindow| now < window.started_at.saturating_add(limit.window_seconds))
+        .unwrap_or(RateWindow {
+            started_at: now,
+            count: 0,
+        });
+    if window.count >= limit.max_calls {
+        return false;
+    }
+    window.count += 1;
+    env.storage().persistent().set(&key, &window);
+    true
+}
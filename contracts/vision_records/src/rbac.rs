@@ -1,4 +1,8 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use crate::{credentials, events};
+use soroban_sdk::{contracttype, symbol_short, vec, Address, Env, String, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const CROLE_CTR: Symbol = symbol_short!("CROLE_CTR");
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -9,6 +13,12 @@ pub enum Permission {
     ManageAccess = 3,
     ManageUsers = 4,
     SystemAdmin = 5,
+    VerifyCredentials = 6,
+    ReadAuditLog = 7,
+    VerifyRecordExistence = 8,
+    ReadResearchData = 9,
+    ReadRecordMetadata = 10,
+    ManageAppointments = 11,
 }
 
 #[contracttype]
@@ -20,13 +30,32 @@ pub enum Role {
     Optometrist = 3,
     Ophthalmologist = 4,
     Admin = 5,
+    Researcher = 6,
+    Auditor = 7,
+    Insurer = 8,
+}
+
+pub fn all_roles(env: &Env) -> Vec<Role> {
+    vec![
+        env,
+        Role::Patient,
+        Role::Staff,
+        Role::Optometrist,
+        Role::Ophthalmologist,
+        Role::Admin,
+        Role::Researcher,
+        Role::Auditor,
+        Role::Insurer,
+    ]
 }
 
+/// The default permission matrix, written to storage on initialization
 pub fn get_base_permissions(env: &Env, role: &Role) -> Vec<Permission> {
     let mut perms = Vec::new(env);
 
     if *role == Role::Admin {
         perms.push_back(Permission::SystemAdmin);
+        perms.push_back(Permission::VerifyCredentials);
     }
 
     if *role == Role::Admin
@@ -37,17 +66,90 @@ pub fn get_base_permissions(env: &Env, role: &Role) -> Vec<Permission> {
         perms.push_back(Permission::ManageUsers);
     }
 
+    if *role == Role::Admin || *role == Role::Staff {
+        perms.push_back(Permission::ManageAppointments);
+    }
+
     if *role == Role::Admin || *role == Role::Ophthalmologist || *role == Role::Optometrist {
         perms.push_back(Permission::WriteRecord);
         perms.push_back(Permission::ManageAccess);
         perms.push_back(Permission::ReadAnyRecord);
     }
 
+    // Oversight roles see who accessed what and record metadata. These limits
+    // cover the permissioned endpoints only: `get_record` stays a public view
+    // and returns the data hash to anyone, as does the ledger itself.
+    if *role == Role::Admin || *role == Role::Auditor {
+        perms.push_back(Permission::ReadAuditLog);
+        perms.push_back(Permission::ReadRecordMetadata);
+    }
+
+    if *role == Role::Admin || *role == Role::Insurer {
+        perms.push_back(Permission::VerifyRecordExistence);
+    }
+
+    if *role == Role::Researcher {
+        perms.push_back(Permission::ReadResearchData);
+    }
+
     // Patients have essentially no specific global permissions, they manage their own implicitly
 
     perms
 }
 
+pub fn role_permissions_key(role: &Role) -> (Symbol, Role) {
+    (symbol_short!("ROLE_PRM"), role.clone())
+}
+
+/// The permissions a role currently carries, from the stored matrix. Roles
+/// without a stored entry fall back to the defaults.
+pub fn get_role_permissions(env: &Env, role: &Role) -> Vec<Permission> {
+    env.storage()
+        .persistent()
+        .get(&role_permissions_key(role))
+        .unwrap_or(get_base_permissions(env, role))
+}
+
+pub fn set_role_permissions(env: &Env, actor: Address, role: Role, permissions: Vec<Permission>) {
+    env.storage()
+        .persistent()
+        .set(&role_permissions_key(&role), &permissions);
+
+    events::publish_role_permissions_set(env, actor, role, permissions);
+}
+
+/// Store the default matrix for every role
+pub fn init_role_permissions(env: &Env) {
+    for role in all_roles(env).iter() {
+        env.storage().persistent().set(
+            &role_permissions_key(&role),
+            &get_base_permissions(env, &role),
+        );
+    }
+}
+
+/// Permissions that let a provider practice on patient records
+pub fn is_clinical_permission(permission: &Permission) -> bool {
+    *permission == Permission::WriteRecord || *permission == Permission::ReadAnyRecord
+}
+
+/// Permissions that let their holder hand out or verify privileges, so only
+/// a `SystemAdmin` may confer them
+pub fn is_privileged_permission(permission: &Permission) -> bool {
+    *permission == Permission::SystemAdmin
+        || *permission == Permission::ManageUsers
+        || *permission == Permission::VerifyCredentials
+}
+
+/// Clinical permissions inherited from a clinical role only apply while the
+/// holder has a valid credential for that role.
+fn is_credentialed_for(env: &Env, user: &Address, role: &Role, permission: &Permission) -> bool {
+    if credentials::is_clinical_role(role) && is_clinical_permission(permission) {
+        return credentials::has_valid_credential(env, user, role);
+    }
+    true
+}
+
 /// Represents an assigned role with specific custom grants or revocations
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -68,6 +170,44 @@ pub struct Delegation {
     pub expires_at: u64, // 0 means never expires
 }
 
+/// A role held in addition to the one in the user's `RoleAssignment`, with
+/// its own expiry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleEntry {
+    pub role: Role,
+    pub expires_at: u64, // 0 means never expires
+}
+
+/// A named role defined by an admin, e.g. "Optical Lab Tech"
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustomRole {
+    pub id: u64,
+    pub name: String,
+    pub permissions: Vec<Permission>,
+    pub created_by: Address,
+    pub created_at: u64,
+}
+
+/// A user's custom role, held next to their `RoleAssignment`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustomRoleAssignment {
+    pub role_id: u64,
+    pub expires_at: u64, // 0 means never expires
+}
+
+/// The delegation of a custom role to someone else
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustomRoleDelegation {
+    pub delegator: Address,
+    pub delegatee: Address,
+    pub role_id: u64,
+    pub expires_at: u64, // 0 means never expires
+}
+
 /// Internal store schema helpers
 pub fn user_assignment_key(user: &Address) -> (soroban_sdk::Symbol, Address) {
     (symbol_short!("ROLE_ASN"), user.clone())
@@ -84,11 +224,34 @@ pub fn delegation_key(
     )
 }
 
+pub fn additional_roles_key(user: &Address) -> (Symbol, Address) {
+    (symbol_short!("ROLES"), user.clone())
+}
+
+pub fn custom_role_key(role_id: u64) -> (Symbol, u64) {
+    (symbol_short!("CROLE"), role_id)
+}
+
+pub fn custom_assignment_key(user: &Address) -> (Symbol, Address) {
+    (symbol_short!("CROLE_ASN"), user.clone())
+}
+
+pub fn custom_delegation_key(
+    delegator: &Address,
+    delegatee: &Address,
+) -> (Symbol, Address, Address) {
+    (
+        symbol_short!("CR_DELEG"),
+        delegator.clone(),
+        delegatee.clone(),
+    )
+}
+
 // ======================== Core RBAC Engine ========================
 
-pub fn assign_role(env: &Env, user: Address, role: Role, expires_at: u64) {
+pub fn assign_role(env: &Env, actor: Address, user: Address, role: Role, expires_at: u64) {
     let assignment = RoleAssignment {
-        role,
+        role: role.clone(),
         custom_grants: Vec::new(env),
         custom_revokes: Vec::new(env),
         expires_at,
@@ -97,6 +260,8 @@ pub fn assign_role(env: &Env, user: Address, role: Role, expires_at: u64) {
     env.storage()
         .persistent()
         .set(&user_assignment_key(&user), &assignment);
+
+    events::publish_role_assigned(env, actor, user, role, expires_at);
 }
 
 /// Retrieve the active assignment for a user, or None if it doesn't exist or is expired
@@ -113,8 +278,91 @@ pub fn get_active_assignment(env: &Env, user: &Address) -> Option<RoleAssignment
     None
 }
 
+/// Remove `user`'s assignment if it has expired. Returns false if there is
+/// no assignment or it is still active.
+pub fn expire_role(env: &Env, actor: Address, user: Address) -> bool {
+    let key = user_assignment_key(&user);
+    if let Some(assignment) = env.storage().persistent().get::<_, RoleAssignment>(&key) {
+        if assignment.expires_at != 0 && assignment.expires_at <= env.ledger().timestamp() {
+            env.storage().persistent().remove(&key);
+            events::publish_role_expired(env, actor, user, assignment.role, assignment.expires_at);
+            return true;
+        }
+    }
+    false
+}
+
+/// Whether `user` currently holds `role`, either as their assigned role or
+/// as an additional one
+pub fn has_role(env: &Env, user: &Address, role: &Role) -> bool {
+    get_active_roles(env, user).contains(role)
+}
+
+fn get_additional_roles(env: &Env, user: &Address) -> Vec<RoleEntry> {
+    env.storage()
+        .persistent()
+        .get(&additional_roles_key(user))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Every role `user` currently holds, without duplicates
+pub fn get_active_roles(env: &Env, user: &Address) -> Vec<Role> {
+    let mut roles = Vec::new(env);
+    if let Some(assignment) = get_active_assignment(env, user) {
+        roles.push_back(assignment.role);
+    }
+    let now = env.ledger().timestamp();
+    for entry in get_additional_roles(env, user).iter() {
+        if (entry.expires_at == 0 || entry.expires_at > now) && !roles.contains(&entry.role) {
+            roles.push_back(entry.role);
+        }
+    }
+    roles
+}
+
+/// Give `user` an extra role next to their assigned one. Adding a role they
+/// already hold as an extra replaces its expiry.
+pub fn add_role(env: &Env, actor: Address, user: Address, role: Role, expires_at: u64) {
+    let mut entries = Vec::new(env);
+    for entry in get_additional_roles(env, &user).iter() {
+        if entry.role != role {
+            entries.push_back(entry);
+        }
+    }
+    entries.push_back(RoleEntry {
+        role: role.clone(),
+        expires_at,
+    });
+    env.storage()
+        .persistent()
+        .set(&additional_roles_key(&user), &entries);
+
+    events::publish_role_added(env, actor, user, role, expires_at);
+}
+
+/// Remove an extra role. Returns false if `user` did not hold it as one.
+pub fn remove_role(env: &Env, actor: Address, user: Address, role: Role) -> bool {
+    let mut entries = get_additional_roles(env, &user);
+    let index = match entries.iter().position(|entry| entry.role == role) {
+        Some(index) => index as u32,
+        None => return false,
+    };
+    entries.remove(index);
+    env.storage()
+        .persistent()
+        .set(&additional_roles_key(&user), &entries);
+
+    events::publish_role_removed(env, actor, user, role);
+    true
+}
+
 /// Set custom permissions for an existing assignment
-pub fn grant_custom_permission(env: &Env, user: Address, permission: Permission) -> Result<(), ()> {
+pub fn grant_custom_permission(
+    env: &Env,
+    actor: Address,
+    user: Address,
+    permission: Permission,
+) -> Result<(), ()> {
     let mut assignment = get_active_assignment(env, &user).ok_or(())?;
 
     // Remove from revokes if present
@@ -128,18 +376,21 @@ pub fn grant_custom_permission(env: &Env, user: Address, permission: Permission)
 
     // Add to grants if not already there
     if !assignment.custom_grants.contains(&permission) {
-        assignment.custom_grants.push_back(permission);
+        assignment.custom_grants.push_back(permission.clone());
     }
 
     env.storage()
         .persistent()
         .set(&user_assignment_key(&user), &assignment);
+
+    events::publish_permission_granted(env, actor, user, permission);
     Ok(())
 }
 
 /// Revoke a permission for a specific user specifically
 pub fn revoke_custom_permission(
     env: &Env,
+    actor: Address,
     user: Address,
     permission: Permission,
 ) -> Result<(), ()> {
@@ -156,12 +407,14 @@ pub fn revoke_custom_permission(
 
     // Add to revokes if not already there
     if !assignment.custom_revokes.contains(&permission) {
-        assignment.custom_revokes.push_back(permission);
+        assignment.custom_revokes.push_back(permission.clone());
     }
 
     env.storage()
         .persistent()
         .set(&user_assignment_key(&user), &assignment);
+
+    events::publish_permission_revoked(env, actor, user, permission);
     Ok(())
 }
 
@@ -176,13 +429,22 @@ pub fn delegate_role(
     let del = Delegation {
         delegator: delegator.clone(),
         delegatee: delegatee.clone(),
-        role,
+        role: role.clone(),
         expires_at,
     };
 
     env.storage()
         .persistent()
         .set(&delegation_key(&delegator, &delegatee), &del);
+
+    events::publish_role_delegated(
+        env,
+        delegator.clone(),
+        delegator,
+        delegatee,
+        role,
+        expires_at,
+    );
 }
 
 /// Retrieve the active delegations for a particular `delegatee` representing `delegator`
@@ -214,18 +476,30 @@ pub fn has_permission(env: &Env, user: &Address, permission: &Permission) -> boo
             return false; // Explicit revoke overrides all basic/grant logic below
         }
 
-        // Did we explicitly grant it?
-        if assignment.custom_grants.contains(permission) {
+        // Did we explicitly grant it? Clinical grants, like custom roles,
+        // need a valid credential of any specialty
+        if assignment.custom_grants.contains(permission)
+            && (!is_clinical_permission(permission)
+                || credentials::has_active_credential(env, user))
+        {
             return true;
         }
+    }
 
-        // Do we get it implicitly through our baseline role hierarchy?
-        if get_base_permissions(env, &assignment.role).contains(permission) {
+    // 2. Do we get it implicitly from any role we hold?
+    for role in get_active_roles(env, user).iter() {
+        if get_role_permissions(env, &role).contains(permission)
+            && is_credentialed_for(env, user, &role, permission)
+        {
             return true;
         }
     }
 
-    false
+    // 3. Fall back to the user's custom role
+    match get_active_custom_assignment(env, user) {
+        Some(assignment) => custom_role_grants(env, user, assignment.role_id, permission),
+        None => false,
+    }
 }
 
 /// Same as has_permission, but also checks if `delegatee` can perform the action on behalf of `delegator`.
@@ -236,9 +510,127 @@ pub fn has_delegated_permission(
     permission: &Permission,
 ) -> bool {
     if let Some(delegation) = get_active_delegation(env, delegator, delegatee) {
-        if get_base_permissions(env, &delegation.role).contains(permission) {
+        if get_role_permissions(env, &delegation.role).contains(permission)
+            && is_credentialed_for(env, delegator, &delegation.role, permission)
+        {
             return true;
         }
     }
+
+    // A custom role can only be exercised by a delegatee while the delegator still holds it
+    if let Some(delegation) = get_active_custom_delegation(env, delegator, delegatee) {
+        if let Some(assignment) = get_active_custom_assignment(env, delegator) {
+            if assignment.role_id == delegation.role_id {
+                return custom_role_grants(env, delegator, delegation.role_id, permission);
+            }
+        }
+    }
     false
 }
+
+// ======================== Custom Roles ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_custom_role_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&CROLE_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&CROLE_CTR, &id);
+    id
+}
+
+pub fn get_custom_role(env: &Env, role_id: u64) -> Option<CustomRole> {
+    env.storage().persistent().get(&custom_role_key(role_id))
+}
+
+pub fn save_custom_role(env: &Env, role: &CustomRole) {
+    env.storage()
+        .persistent()
+        .set(&custom_role_key(role.id), role);
+}
+
+pub fn assign_custom_role(env: &Env, actor: Address, user: Address, role_id: u64, expires_at: u64) {
+    let assignment = CustomRoleAssignment {
+        role_id,
+        expires_at,
+    };
+    env.storage()
+        .persistent()
+        .set(&custom_assignment_key(&user), &assignment);
+
+    events::publish_custom_role_assigned(env, actor, user, role_id, expires_at);
+}
+
+/// Remove `user`'s custom role. Returns false if they had none.
+pub fn revoke_custom_role(env: &Env, actor: Address, user: Address) -> bool {
+    let key = custom_assignment_key(&user);
+    match env
+        .storage()
+        .persistent()
+        .get::<_, CustomRoleAssignment>(&key)
+    {
+        Some(assignment) => {
+            env.storage().persistent().remove(&key);
+            events::publish_custom_role_revoked(env, actor, user, assignment.role_id);
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn get_active_custom_assignment(env: &Env, user: &Address) -> Option<CustomRoleAssignment> {
+    env.storage()
+        .persistent()
+        .get::<_, CustomRoleAssignment>(&custom_assignment_key(user))
+        .filter(|a| a.expires_at == 0 || a.expires_at > env.ledger().timestamp())
+}
+
+pub fn delegate_custom_role(
+    env: &Env,
+    delegator: Address,
+    delegatee: Address,
+    role_id: u64,
+    expires_at: u64,
+) {
+    let del = CustomRoleDelegation {
+        delegator: delegator.clone(),
+        delegatee: delegatee.clone(),
+        role_id,
+        expires_at,
+    };
+    env.storage()
+        .persistent()
+        .set(&custom_delegation_key(&delegator, &delegatee), &del);
+
+    events::publish_custom_role_delegated(
+        env,
+        delegator.clone(),
+        delegator,
+        delegatee,
+        role_id,
+        expires_at,
+    );
+}
+
+pub fn get_active_custom_delegation(
+    env: &Env,
+    delegator: &Address,
+    delegatee: &Address,
+) -> Option<CustomRoleDelegation> {
+    env.storage()
+        .persistent()
+        .get::<_, CustomRoleDelegation>(&custom_delegation_key(delegator, delegatee))
+        .filter(|d| d.expires_at == 0 || d.expires_at > env.ledger().timestamp())
+}
+
+/// Whether custom role `role_id` gives `holder` the permission. Custom roles
+/// are not tied to a specialty, so clinical permissions need any valid
+/// credential.
+fn custom_role_grants(env: &Env, holder: &Address, role_id: u64, permission: &Permission) -> bool {
+    match get_custom_role(env, role_id) {
+        Some(role) => {
+            role.permissions.contains(permission)
+                && (!is_clinical_permission(permission)
+                    || credentials::has_active_credential(env, holder))
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,211 @@
+use crate::AccessLevel;
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const REF_CTR: Symbol = symbol_short!("REF_CTR");
+
+/// How long a referral's scoped read grant lasts (30 days)
+pub const REFERRAL_ACCESS_DURATION: u64 = 30 * 24 * 60 * 60;
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// How soon the patient should be seen by the specialist
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReferralUrgency {
+    Routine,
+    Urgent,
+    Emergent,
+}
+
+/// Lifecycle of a referral: Pending -> Accepted -> Completed, or Pending -> Declined
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReferralStatus {
+    Pending,
+    Accepted,
+    Declined,
+    Completed,
+}
+
+/// A referral from an optometrist to an ophthalmologist
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Referral {
+    pub id: u64,
+    pub patient: Address,
+    pub referrer: Address,
+    pub specialist: Address,
+    pub reason: String,
+    pub urgency: ReferralUrgency,
+    pub record_ids: Vec<u64>,
+    pub status: ReferralStatus,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub access_expires_at: u64,
+    /// The resulting `Diagnosis` or `Surgery` record, set on completion
+    pub outcome_record_id: Option<u64>,
+}
+
+/// Access to a single record, independent of any patient-wide `AccessGrant`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RecordGrant {
+    pub record_id: u64,
+    pub grantee: Address,
+    pub level: AccessLevel,
+    pub granted_at: u64,
+    pub expires_at: u64,
+    /// The referrals this grant was made for
+    pub referral_ids: Vec<u64>,
+}
+
+/// Internal store schema helpers
+pub fn referral_key(referral_id: u64) -> (Symbol, u64) {
+    (symbol_short!("REFERRAL"), referral_id)
+}
+
+pub fn patient_referrals_key(patient: &Address) -> (Symbol, Address) {
+    (symbol_short!("PAT_REF"), patient.clone())
+}
+
+pub fn provider_referrals_key(provider: &Address) -> (Symbol, Address) {
+    (symbol_short!("PRV_REF"), provider.clone())
+}
+
+pub fn record_grant_key(record_id: u64, grantee: &Address) -> (Symbol, u64, Address) {
+    (symbol_short!("REC_GRT"), record_id, grantee.clone())
+}
+
+// ======================== Referrals ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_referral_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&REF_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&REF_CTR, &id);
+    id
+}
+
+pub fn get_referral(env: &Env, referral_id: u64) -> Option<Referral> {
+    env.storage().persistent().get(&referral_key(referral_id))
+}
+
+pub fn save_referral(env: &Env, referral: &Referral) {
+    env.storage()
+        .persistent()
+        .set(&referral_key(referral.id), referral);
+}
+
+fn append_id(env: &Env, key: &(Symbol, Address), id: u64) {
+    let mut ids: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    ids.push_back(id);
+    env.storage().persistent().set(key, &ids);
+}
+
+/// Store a new referral and index it under the patient and both providers
+pub fn create_referral(env: &Env, referral: &Referral) {
+    save_referral(env, referral);
+    append_id(env, &patient_referrals_key(&referral.patient), referral.id);
+    append_id(
+        env,
+        &provider_referrals_key(&referral.referrer),
+        referral.id,
+    );
+    append_id(
+        env,
+        &provider_referrals_key(&referral.specialist),
+        referral.id,
+    );
+}
+
+pub fn get_patient_referrals(env: &Env, patient: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&patient_referrals_key(patient))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn get_provider_referrals(env: &Env, provider: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&provider_referrals_key(provider))
+        .unwrap_or(Vec::new(env))
+}
+
+// ======================== Record-Scoped Grants ========================
+
+/// Grant `grantee` access to a record on behalf of a referral. An existing
+/// grant keeps the later expiry and the higher level of the two.
+pub fn grant_record_access(
+    env: &Env,
+    record_id: u64,
+    grantee: &Address,
+    level: AccessLevel,
+    expires_at: u64,
+    referral_id: u64,
+) {
+    let key = record_grant_key(record_id, grantee);
+    let grant = match env.storage().persistent().get::<_, RecordGrant>(&key) {
+        Some(mut grant) => {
+            if crate::access_rank(&level) > crate::access_rank(&grant.level) {
+                grant.level = level;
+            }
+            grant.expires_at = grant.expires_at.max(expires_at);
+            if !grant.referral_ids.contains(referral_id) {
+                grant.referral_ids.push_back(referral_id);
+            }
+            grant
+        }
+        None => RecordGrant {
+            record_id,
+            grantee: grantee.clone(),
+            level,
+            granted_at: env.ledger().timestamp(),
+            expires_at,
+            referral_ids: Vec::from_array(env, [referral_id]),
+        },
+    };
+    env.storage().persistent().set(&key, &grant);
+}
+
+/// Withdraw a referral's share of a record grant. The grant is removed once
+/// no referral backs it; otherwise it lasts as long as the remaining ones.
+pub fn revoke_record_access(env: &Env, record_id: u64, grantee: &Address, referral_id: u64) {
+    let key = record_grant_key(record_id, grantee);
+    let mut grant = match env.storage().persistent().get::<_, RecordGrant>(&key) {
+        Some(grant) => grant,
+        None => return,
+    };
+    if let Some(index) = grant.referral_ids.first_index_of(referral_id) {
+        grant.referral_ids.remove(index);
+    } else {
+        return;
+    }
+
+    if grant.referral_ids.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    grant.expires_at = grant
+        .referral_ids
+        .iter()
+        .filter_map(|id| get_referral(env, id))
+        .map(|referral| referral.access_expires_at)
+        .max()
+        .unwrap_or(0);
+    env.storage().persistent().set(&key, &grant);
+}
+
+/// The unexpired record-scoped access level of `grantee`, if any
+pub fn get_record_access(env: &Env, record_id: u64, grantee: &Address) -> AccessLevel {
+    if let Some(grant) = env
+        .storage()
+        .persistent()
+        .get::<_, RecordGrant>(&record_grant_key(record_id, grantee))
+    {
+        if grant.expires_at > env.ledger().timestamp() {
+            return grant.level;
+        }
+    }
+    AccessLevel::None
+}
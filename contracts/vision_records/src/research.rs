@@ -0,0 +1,171 @@
+use crate::RecordType;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, String, Symbol, Vec};
+
+// ── Storage keys ──────────────────────────────────────────────
+pub const STUDY_CTR: Symbol = symbol_short!("STUDY_CTR");
+
+/// Study id under which a patient opts in to every study
+pub const ALL_STUDIES: u64 = 0;
+
+// ── Types ─────────────────────────────────────────────────────
+
+/// A research study registered by a researcher
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Study {
+    pub id: u64,
+    pub title: String,
+    pub investigator: Address,
+    pub created_at: u64,
+}
+
+/// A patient's opt-in to share record hashes with one study, or with every
+/// study when `study_id` is `ALL_STUDIES`
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ResearchConsent {
+    pub patient: Address,
+    pub study_id: u64,
+    /// Record types covered; empty means all types
+    pub record_types: Vec<RecordType>,
+    pub opted_in_at: u64,
+}
+
+/// A shared record, stripped of patient and provider addresses. The data
+/// hash is replaced by a per-study digest of it.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CohortRecord {
+    pub record_type: RecordType,
+    pub data_digest: BytesN<32>,
+    pub created_at: u64,
+}
+
+/// One consenting patient of a study, under their study pseudonym
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CohortMember {
+    pub pseudonym: BytesN<32>,
+    pub records: Vec<CohortRecord>,
+}
+
+/// Internal store schema helpers
+pub fn study_key(study_id: u64) -> (Symbol, u64) {
+    (symbol_short!("STUDY"), study_id)
+}
+
+/// Mixed into every pseudonym and data digest of the study. No endpoint
+/// returns it, but like all contract storage it can be read off the ledger.
+pub fn salt_key(study_id: u64) -> (Symbol, u64) {
+    (symbol_short!("RS_SALT"), study_id)
+}
+
+pub fn consent_key(patient: &Address, study_id: u64) -> (Symbol, Address, u64) {
+    (symbol_short!("RS_CONS"), patient.clone(), study_id)
+}
+
+/// Patients currently opted in under a study id (including `ALL_STUDIES`)
+pub fn participants_key(study_id: u64) -> (Symbol, u64) {
+    (symbol_short!("RS_PATS"), study_id)
+}
+
+// ======================== Studies ========================
+
+#[allow(clippy::arithmetic_side_effects)]
+pub fn next_study_id(env: &Env) -> u64 {
+    let id: u64 = env.storage().instance().get(&STUDY_CTR).unwrap_or(0) + 1;
+    env.storage().instance().set(&STUDY_CTR, &id);
+    id
+}
+
+pub fn get_study(env: &Env, study_id: u64) -> Option<Study> {
+    env.storage().persistent().get(&study_key(study_id))
+}
+
+pub fn save_study(env: &Env, study: &Study) {
+    env.storage().persistent().set(&study_key(study.id), study);
+}
+
+/// Draw a new study's salt from the contract's PRNG, so it cannot be
+/// recomputed from the study's public fields
+pub fn create_salt(env: &Env, study_id: u64) {
+    let salt: BytesN<32> = env.prng().gen();
+    env.storage().persistent().set(&salt_key(study_id), &salt);
+}
+
+/// sha256(salt ‖ `value`) under the study's salt
+fn salted_digest(env: &Env, study_id: u64, value: &Bytes) -> BytesN<32> {
+    let salt: BytesN<32> = env
+        .storage()
+        .persistent()
+        .get(&salt_key(study_id))
+        .unwrap_or(BytesN::from_array(env, &[0; 32]));
+    let mut preimage = Bytes::from_array(env, &salt.to_array());
+    preimage.append(value);
+    env.crypto().sha256(&preimage).into()
+}
+
+/// The patient's identifier within a study: sha256(salt ‖ XDR(address))
+pub fn pseudonym(env: &Env, study_id: u64, patient: &Address) -> BytesN<32> {
+    salted_digest(env, study_id, &patient.clone().to_xdr(env))
+}
+
+/// A record's data hash as shown to a study: sha256(salt ‖ XDR(data_hash)).
+/// Equal within the study, but not comparable with other studies or with
+/// the stored record.
+pub fn data_digest(env: &Env, study_id: u64, data_hash: &String) -> BytesN<32> {
+    salted_digest(env, study_id, &data_hash.clone().to_xdr(env))
+}
+
+// ======================== Opt-in ========================
+
+pub fn get_consent(env: &Env, patient: &Address, study_id: u64) -> Option<ResearchConsent> {
+    env.storage()
+        .persistent()
+        .get(&consent_key(patient, study_id))
+}
+
+pub fn get_participants(env: &Env, study_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&participants_key(study_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn opt_in(env: &Env, consent: &ResearchConsent) {
+    env.storage()
+        .persistent()
+        .set(&consent_key(&consent.patient, consent.study_id), consent);
+
+    let mut participants = get_participants(env, consent.study_id);
+    if !participants.contains(&consent.patient) {
+        participants.push_back(consent.patient.clone());
+        env.storage()
+            .persistent()
+            .set(&participants_key(consent.study_id), &participants);
+    }
+}
+
+/// Remove the patient's opt-in. Returns false if there was none.
+pub fn withdraw(env: &Env, patient: &Address, study_id: u64) -> bool {
+    let key = consent_key(patient, study_id);
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+    env.storage().persistent().remove(&key);
+
+    let mut participants = get_participants(env, study_id);
+    if let Some(index) = participants.first_index_of(patient) {
+        participants.remove(index);
+        env.storage()
+            .persistent()
+            .set(&participants_key(study_id), &participants);
+    }
+    true
+}
+
+/// Whether a consent covers records of `record_type`
+pub fn covers(consent: &ResearchConsent, record_type: &RecordType) -> bool {
+    consent.record_types.is_empty() || consent.record_types.contains(record_type)
+}
@@ -0,0 +1,305 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env};
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::vec;
+use vision_records::{
+    AccessLevel, AppointmentStatus, ContractError, Permission, PurposeOfUse, RecordType, Role,
+};
+
+const GRACE: u64 = 86_400;
+
+#[test]
+fn test_confirmation_grants_access_for_the_appointment_window() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let staff = create_test_user(&ctx, Role::Staff, "Front Desk");
+
+    let id = ctx
+        .client
+        .book_appointment(&staff, &patient, &provider, &5_000, &6_000);
+    assert_eq!(
+        ctx.client.get_appointment(&id).status,
+        AppointmentStatus::Booked
+    );
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::None
+    );
+    assert_eq!(
+        ctx.client.get_patient_appointments(&patient),
+        vec![&ctx.env, id]
+    );
+    assert_eq!(
+        ctx.client.get_provider_appointments(&provider),
+        vec![&ctx.env, id]
+    );
+
+    ctx.client.confirm_appointment(&patient, &id);
+    let appointment = ctx.client.get_appointment(&id);
+    assert_eq!(appointment.status, AppointmentStatus::Confirmed);
+    assert_eq!(appointment.grant_expires_at, 6_000 + GRACE);
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::Read
+    );
+    assert_eq!(
+        ctx.client.get_grant_purposes(&patient, &provider),
+        vec![&ctx.env, PurposeOfUse::Treatment]
+    );
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    ctx.client
+        .access_record(&provider, &record_id, &PurposeOfUse::Treatment);
+
+    ctx.env.ledger().set_timestamp(6_000 + GRACE);
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::None
+    );
+}
+
+#[test]
+fn test_cancellation_ends_only_the_appointment_grant() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let staff = create_test_user(&ctx, Role::Staff, "Front Desk");
+
+    let id = ctx
+        .client
+        .book_appointment(&staff, &patient, &provider, &5_000, &6_000);
+    ctx.client.confirm_appointment(&patient, &id);
+    ctx.client.cancel_appointment(&provider, &id);
+    assert_eq!(
+        ctx.client.get_appointment(&id).status,
+        AppointmentStatus::Cancelled
+    );
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::None
+    );
+
+    // A standing grant that already covers the appointment is left alone
+    ctx.client.grant_access(
+        &patient,
+        &patient,
+        &provider,
+        &AccessLevel::Write,
+        &(10 * GRACE),
+    );
+    let id = ctx
+        .client
+        .book_appointment(&staff, &patient, &provider, &5_000, &6_000);
+    ctx.client.confirm_appointment(&patient, &id);
+    assert_eq!(ctx.client.get_appointment(&id).grant_expires_at, 0);
+    ctx.client.cancel_appointment(&staff, &id);
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::Write
+    );
+}
+
+#[test]
+fn test_no_show_after_start() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let staff = create_test_user(&ctx, Role::Staff, "Front Desk");
+
+    let id = ctx
+        .client
+        .book_appointment(&staff, &patient, &provider, &5_000, &6_000);
+    let res = ctx.client.try_mark_no_show(&provider, &id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    ctx.client.confirm_appointment(&patient, &id);
+    let res = ctx.client.try_mark_no_show(&provider, &id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx.client.try_mark_no_show(&patient, &id);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    ctx.env.ledger().set_timestamp(5_500);
+    ctx.client.mark_no_show(&provider, &id);
+    assert_eq!(
+        ctx.client.get_appointment(&id).status,
+        AppointmentStatus::NoShow
+    );
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::None
+    );
+    let res = ctx.client.try_cancel_appointment(&patient, &id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_appointment_validation() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let staff = create_test_user(&ctx, Role::Staff, "Front Desk");
+    let other = create_test_user(&ctx, Role::Patient, "Other");
+
+    // Booking is a staff task, and only with clinical providers
+    let res = ctx
+        .client
+        .try_book_appointment(&patient, &patient, &provider, &5_000, &6_000);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx
+        .client
+        .try_book_appointment(&staff, &patient, &staff, &5_000, &6_000);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx
+        .client
+        .try_book_appointment(&staff, &patient, &provider, &500, &6_000);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx
+        .client
+        .try_book_appointment(&staff, &patient, &provider, &6_000, &5_000);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let id = ctx
+        .client
+        .book_appointment(&staff, &patient, &provider, &5_000, &6_000);
+    let res = ctx.client.try_confirm_appointment(&other, &id);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_cancel_appointment(&other, &id);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    ctx.client.confirm_appointment(&patient, &id);
+    let res = ctx.client.try_confirm_appointment(&patient, &id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let res = ctx.client.try_get_appointment(&99);
+    assert_eq!(res.err(), Some(Ok(ContractError::AppointmentNotFound)));
+}
+
+#[test]
+fn test_appointment_grant_starts_shortly_before_the_visit() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let staff = create_test_user(&ctx, Role::Staff, "Front Desk");
+    let start = 90 * GRACE;
+    let author = create_test_user(&ctx, Role::Optometrist, "Author");
+    let record_id = create_test_record(
+        &ctx,
+        &author,
+        &patient,
+        &author,
+        RecordType::Examination,
+        "QmExam",
+    );
+    // Without the blanket `ReadAnyRecord`, reads go through the grant
+    ctx.client.set_role_permissions(
+        &ctx.admin,
+        &Role::Optometrist,
+        &vec![&ctx.env, Permission::WriteRecord],
+    );
+
+    // Confirming a visit months ahead does not open the chart until the day before
+    let id = ctx
+        .client
+        .book_appointment(&staff, &patient, &provider, &start, &(start + 3_600));
+    ctx.client.confirm_appointment(&patient, &id);
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::None
+    );
+    let res = ctx
+        .client
+        .try_access_record(&provider, &record_id, &PurposeOfUse::Treatment);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+
+    ctx.env.ledger().set_timestamp(start - GRACE);
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::Read
+    );
+    ctx.client
+        .access_record(&provider, &record_id, &PurposeOfUse::Treatment);
+}
+
+#[test]
+fn test_cancelling_before_the_window_removes_the_grant() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let staff = create_test_user(&ctx, Role::Staff, "Front Desk");
+    let start = 90 * GRACE;
+
+    let id = ctx
+        .client
+        .book_appointment(&staff, &patient, &provider, &start, &(start + 3_600));
+    ctx.client.confirm_appointment(&patient, &id);
+    ctx.client.cancel_appointment(&patient, &id);
+
+    // The grant never took effect, so there are no keys to rotate away
+    assert_eq!(ctx.client.get_key_epoch(&patient), 0);
+
+    ctx.env.ledger().set_timestamp(start);
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::None
+    );
+}
+
+#[test]
+fn test_confirmation_extends_existing_grant_and_cancel_restores_it() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let staff = create_test_user(&ctx, Role::Staff, "Front Desk");
+    let purposes = vec![&ctx.env, PurposeOfUse::Treatment, PurposeOfUse::Payment];
+    ctx.client.grant_access_for_purposes(
+        &patient,
+        &patient,
+        &provider,
+        &AccessLevel::Full,
+        &2_000,
+        &purposes,
+    );
+    let epoch = ctx.client.get_key_epoch(&patient);
+
+    // The shorter Full grant is stretched, not replaced by a Read grant
+    let id = ctx
+        .client
+        .book_appointment(&staff, &patient, &provider, &5_000, &6_000);
+    ctx.client.confirm_appointment(&patient, &id);
+    assert_eq!(
+        ctx.client.get_appointment(&id).grant_expires_at,
+        6_000 + GRACE
+    );
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::Full
+    );
+    assert_eq!(ctx.client.get_grant_purposes(&patient, &provider), purposes);
+
+    // Cancelling gives back the original grant without a key rotation
+    ctx.client.cancel_appointment(&staff, &id);
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::Full
+    );
+    assert_eq!(ctx.client.get_key_epoch(&patient), epoch);
+    ctx.env.ledger().set_timestamp(3_000);
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::None
+    );
+}
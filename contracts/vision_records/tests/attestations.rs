@@ -0,0 +1,126 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::Address;
+use vision_records::{ClaimType, ContractError, RecordType, Role};
+
+#[test]
+fn test_attestations_verify_without_record_access() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+
+    let id = ctx.client.issue_attestation(
+        &provider,
+        &patient,
+        &ClaimType::DrivingAcuityStandard,
+        &5_000,
+    );
+    let attestation = ctx.client.get_attestation(&id);
+    assert_eq!(attestation.issuer, provider);
+    assert_eq!(ctx.client.get_subject_attestations(&patient).len(), 1);
+
+    // A verifier with no role or grant checks the claim, not the records
+    assert!(ctx
+        .client
+        .verify_attestation(&id, &patient, &ClaimType::DrivingAcuityStandard));
+    assert!(!ctx
+        .client
+        .verify_attestation(&id, &patient, &ClaimType::ValidContactLensRx));
+    assert!(!ctx.client.verify_attestation(
+        &id,
+        &Address::generate(&ctx.env),
+        &ClaimType::DrivingAcuityStandard
+    ));
+    assert!(ctx
+        .client
+        .has_valid_attestation(&patient, &ClaimType::DrivingAcuityStandard));
+
+    ctx.env.ledger().set_timestamp(5_000);
+    assert!(!ctx
+        .client
+        .verify_attestation(&id, &patient, &ClaimType::DrivingAcuityStandard));
+    assert!(!ctx
+        .client
+        .has_valid_attestation(&patient, &ClaimType::DrivingAcuityStandard));
+}
+
+#[test]
+fn test_attestation_revocation() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let issuer = create_test_user(&ctx, Role::Ophthalmologist, "Issuer");
+    let other = create_test_user(&ctx, Role::Optometrist, "Other");
+    let first =
+        ctx.client
+            .issue_attestation(&issuer, &patient, &ClaimType::ValidGlassesRx, &86_400);
+    let second =
+        ctx.client
+            .issue_attestation(&issuer, &patient, &ClaimType::ValidGlassesRx, &86_400);
+
+    let res = ctx.client.try_revoke_attestation(&other, &first);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    ctx.client.revoke_attestation(&issuer, &first);
+    assert!(ctx.client.get_attestation(&first).revoked);
+    assert!(!ctx
+        .client
+        .verify_attestation(&first, &patient, &ClaimType::ValidGlassesRx));
+    let res = ctx.client.try_revoke_attestation(&issuer, &first);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // The other attestation still stands until an admin pulls it
+    assert!(ctx
+        .client
+        .has_valid_attestation(&patient, &ClaimType::ValidGlassesRx));
+    ctx.client.revoke_attestation(&ctx.admin, &second);
+    assert!(!ctx
+        .client
+        .has_valid_attestation(&patient, &ClaimType::ValidGlassesRx));
+}
+
+#[test]
+fn test_issuance_requires_role_and_credential() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let claim = ClaimType::VisionScreeningPassed;
+
+    let res = ctx
+        .client
+        .try_issue_attestation(&staff, &patient, &claim, &86_400);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    // Admins can write records but hold no clinical credential
+    let res = ctx
+        .client
+        .try_issue_attestation(&ctx.admin, &patient, &claim, &86_400);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    let res =
+        ctx.client
+            .try_issue_attestation(&provider, &Address::generate(&ctx.env), &claim, &86_400);
+    assert_eq!(res, Err(Ok(ContractError::UserNotFound)));
+    let res = ctx
+        .client
+        .try_issue_attestation(&provider, &patient, &claim, &0);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    ctx.client.suspend_credential(&ctx.admin, &provider);
+    let res = ctx
+        .client
+        .try_issue_attestation(&provider, &patient, &claim, &86_400);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_get_attestation(&99);
+    assert_eq!(res.err(), Some(Ok(ContractError::AttestationNotFound)));
+}
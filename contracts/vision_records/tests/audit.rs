@@ -0,0 +1,112 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env};
+use soroban_sdk::testutils::Ledger;
+use vision_records::{AccessLevel, ContractError, PurposeOfUse, RecordType, Role};
+
+#[test]
+fn test_access_record_appends_audit_entry() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(5_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let purpose = PurposeOfUse::Treatment;
+
+    let record = ctx.client.access_record(&provider, &record_id, &purpose);
+    assert_eq!(record.id, record_id);
+    assert_eq!(ctx.client.get_access_log_count(&patient, &patient), 1);
+
+    let log = ctx.client.get_access_log(&patient, &patient, &0, &10);
+    assert_eq!(log.len(), 1);
+    let entry = log.get(0).unwrap();
+    assert_eq!(entry.record_id, record_id);
+    assert_eq!(entry.actor, provider);
+    assert_eq!(entry.purpose, purpose);
+    assert_eq!(entry.timestamp, 5_000);
+}
+
+#[test]
+fn test_access_record_requires_authorization() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let stranger = create_test_user(&ctx, Role::Staff, "Stranger");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let purpose = PurposeOfUse::Payment;
+
+    let res = ctx
+        .client
+        .try_access_record(&stranger, &record_id, &purpose);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+    assert_eq!(ctx.client.get_access_log_count(&patient, &patient), 0);
+
+    // A patient-wide grant authorizes the read
+    ctx.client
+        .grant_access(&patient, &patient, &stranger, &AccessLevel::Read, &3600);
+    ctx.client.access_record(&stranger, &record_id, &purpose);
+    assert_eq!(ctx.client.get_access_log_count(&patient, &patient), 1);
+
+    let res = ctx.client.try_access_record(&stranger, &99, &purpose);
+    assert_eq!(res.err(), Some(Ok(ContractError::RecordNotFound)));
+
+    // Like the log, its size is hidden from other callers
+    let res = ctx.client.try_get_access_log_count(&stranger, &patient);
+    assert_eq!(res.err(), Some(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_access_log_paging_and_visibility() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Ophthalmologist, "Provider");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Diagnosis,
+        "QmDiag",
+    );
+    let purpose = PurposeOfUse::Treatment;
+    for _ in 0..5 {
+        ctx.client.access_record(&provider, &record_id, &purpose);
+    }
+    ctx.client.access_record(&patient, &record_id, &purpose);
+
+    assert_eq!(
+        ctx.client.get_access_log(&patient, &patient, &0, &4).len(),
+        4
+    );
+    let tail = ctx.client.get_access_log(&patient, &patient, &4, &4);
+    assert_eq!(tail.len(), 2);
+    assert_eq!(tail.get(1).unwrap().actor, patient);
+    assert_eq!(
+        ctx.client.get_access_log(&patient, &patient, &6, &4).len(),
+        0
+    );
+
+    // Admins may review the log, other providers may not
+    assert_eq!(
+        ctx.client
+            .get_access_log(&ctx.admin, &patient, &0, &10)
+            .len(),
+        6
+    );
+    let res = ctx.client.try_get_access_log(&provider, &patient, &0, &10);
+    assert_eq!(res.err(), Some(Ok(ContractError::Unauthorized)));
+}
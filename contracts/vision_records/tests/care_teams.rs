@@ -0,0 +1,136 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, String};
+use vision_records::{AccessLevel, ContractError, GrantTarget, PurposeOfUse, RecordType, Role};
+
+#[test]
+fn test_team_grant_covers_current_members() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let caregiver = create_test_user(&ctx, Role::Patient, "Caregiver");
+    let coordinator = create_test_user(&ctx, Role::Staff, "Coordinator");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+
+    let team_id = ctx
+        .client
+        .create_care_team(&patient, &String::from_str(&ctx.env, "Glaucoma care"));
+    ctx.client
+        .add_care_team_member(&patient, &team_id, &caregiver);
+    ctx.client
+        .add_care_team_member(&patient, &team_id, &coordinator);
+    ctx.client.grant_group_access(
+        &patient,
+        &patient,
+        &GrantTarget::CareTeam(team_id),
+        &AccessLevel::Read,
+        &3600,
+    );
+
+    assert_eq!(ctx.client.get_patient_care_teams(&patient).len(), 1);
+    assert_eq!(ctx.client.get_member_care_teams(&caregiver).len(), 1);
+    assert_eq!(ctx.client.get_care_team(&team_id).members.len(), 2);
+    assert_eq!(
+        ctx.client.check_access(&patient, &caregiver),
+        AccessLevel::Read
+    );
+    ctx.client
+        .access_record(&coordinator, &record_id, &PurposeOfUse::Treatment);
+
+    // Removal takes effect without touching the grant
+    ctx.client
+        .remove_care_team_member(&patient, &team_id, &coordinator);
+    assert_eq!(
+        ctx.client.check_access(&patient, &coordinator),
+        AccessLevel::None
+    );
+    let res = ctx
+        .client
+        .try_access_record(&coordinator, &record_id, &PurposeOfUse::Treatment);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+    assert_eq!(ctx.client.get_member_care_teams(&coordinator).len(), 0);
+}
+
+#[test]
+fn test_team_grant_expires_as_a_unit() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let first = create_test_user(&ctx, Role::Staff, "First");
+    let second = create_test_user(&ctx, Role::Staff, "Second");
+
+    let team_id = ctx
+        .client
+        .create_care_team(&patient, &String::from_str(&ctx.env, "Family"));
+    ctx.client.add_care_team_member(&patient, &team_id, &first);
+    ctx.client.add_care_team_member(&patient, &team_id, &second);
+    ctx.client.grant_group_access(
+        &patient,
+        &patient,
+        &GrantTarget::CareTeam(team_id),
+        &AccessLevel::Write,
+        &500,
+    );
+    assert_eq!(
+        ctx.client.check_access(&patient, &second),
+        AccessLevel::Write
+    );
+
+    ctx.env.ledger().set_timestamp(1_500);
+    assert_eq!(ctx.client.check_access(&patient, &first), AccessLevel::None);
+    assert_eq!(
+        ctx.client.check_access(&patient, &second),
+        AccessLevel::None
+    );
+    assert_eq!(ctx.client.get_group_grants(&patient).len(), 0);
+}
+
+#[test]
+fn test_care_team_validation() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let other = create_test_user(&ctx, Role::Patient, "Other");
+    let member = create_test_user(&ctx, Role::Staff, "Member");
+    let team_id = ctx
+        .client
+        .create_care_team(&patient, &String::from_str(&ctx.env, "Team"));
+    ctx.client.add_care_team_member(&patient, &team_id, &member);
+
+    // Only the owning patient curates the team or grants to it
+    let res = ctx
+        .client
+        .try_add_care_team_member(&other, &team_id, &other);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_grant_group_access(
+        &other,
+        &other,
+        &GrantTarget::CareTeam(team_id),
+        &AccessLevel::Read,
+        &3600,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let res = ctx
+        .client
+        .try_add_care_team_member(&patient, &team_id, &member);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx
+        .client
+        .try_add_care_team_member(&patient, &team_id, &Address::generate(&ctx.env));
+    assert_eq!(res, Err(Ok(ContractError::UserNotFound)));
+    let res = ctx.client.try_get_care_team(&99);
+    assert_eq!(res.err(), Some(Ok(ContractError::CareTeamNotFound)));
+    let res = ctx
+        .client
+        .try_create_care_team(&patient, &String::from_str(&ctx.env, ""));
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env, TestContext};
+use soroban_sdk::{map, vec, BytesN, String};
+use vision_records::merkle;
+use vision_records::{AccessLevel, ClaimLine, ClaimStatus, ContractError, RecordType, Role};
+
+fn record_hash(ctx: &TestContext, record_id: u64) -> BytesN<32> {
+    let record = ctx.client.get_record(&record_id);
+    merkle::leaf_hash(&ctx.env, &merkle::leaf_of(&record))
+}
+
+fn line(ctx: &TestContext, record_id: u64, record_hash: BytesN<32>) -> ClaimLine {
+    ClaimLine {
+        record_id,
+        record_hash,
+        procedure_code: String::from_str(&ctx.env, "92014"),
+    }
+}
+
+#[test]
+fn test_claim_is_verified_and_approved() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let insurer = create_test_user(&ctx, Role::Insurer, "Insurer");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let lines = vec![
+        &ctx.env,
+        line(&ctx, record_id, record_hash(&ctx, record_id)),
+    ];
+
+    let claim_id = ctx
+        .client
+        .submit_claim(&provider, &provider, &patient, &insurer, &lines, &12_500);
+    assert_eq!(
+        ctx.client.get_insurer_claims(&insurer),
+        vec![&ctx.env, claim_id]
+    );
+    assert_eq!(ctx.client.get_provider_claims(&provider).len(), 1);
+    assert_eq!(ctx.client.get_patient_claims(&patient).len(), 1);
+
+    assert!(ctx.client.verify_claim(&insurer, &claim_id));
+    ctx.client.approve_claim(&insurer, &claim_id);
+    let claim = ctx.client.get_claim(&patient, &claim_id);
+    assert_eq!(claim.status, ClaimStatus::Approved);
+    assert_eq!(claim.amount, 12_500);
+
+    // Verification gives the insurer no access to the chart
+    assert_eq!(
+        ctx.client.check_access(&patient, &insurer),
+        AccessLevel::None
+    );
+    let res = ctx.client.try_deny_claim(&insurer, &claim_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_amended_record_fails_verification() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let insurer = create_test_user(&ctx, Role::Insurer, "Insurer");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let lines = vec![
+        &ctx.env,
+        line(&ctx, record_id, record_hash(&ctx, record_id)),
+    ];
+    let claim_id = ctx
+        .client
+        .submit_claim(&provider, &provider, &patient, &insurer, &lines, &12_500);
+
+    // A revocation queues the record for re-encryption, which changes its hash
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+    ctx.client
+        .grant_access(&patient, &patient, &staff, &AccessLevel::Read, &3600);
+    ctx.client.revoke_access(&patient, &staff);
+    ctx.client.rotate_record_key(
+        &provider,
+        &record_id,
+        &String::from_str(&ctx.env, "QmExamV2"),
+        &map![&ctx.env],
+    );
+
+    assert!(!ctx.client.verify_claim(&insurer, &claim_id));
+    let res = ctx.client.try_approve_claim(&insurer, &claim_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    ctx.client.deny_claim(&insurer, &claim_id);
+    assert_eq!(
+        ctx.client.get_claim(&insurer, &claim_id).status,
+        ClaimStatus::Denied
+    );
+}
+
+#[test]
+fn test_claim_validation() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let insurer = create_test_user(&ctx, Role::Insurer, "Insurer");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let hash = record_hash(&ctx, record_id);
+    let lines = vec![&ctx.env, line(&ctx, record_id, hash.clone())];
+
+    let res = ctx.client.try_submit_claim(
+        &provider,
+        &provider,
+        &patient,
+        &insurer,
+        &vec![
+            &ctx.env,
+            line(&ctx, record_id, BytesN::from_array(&ctx.env, &[0; 32])),
+        ],
+        &12_500,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let other_patient = create_test_user(&ctx, Role::Patient, "Other Patient");
+    let res = ctx.client.try_submit_claim(
+        &provider,
+        &provider,
+        &other_patient,
+        &insurer,
+        &lines,
+        &12_500,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx.client.try_submit_claim(
+        &provider,
+        &provider,
+        &patient,
+        &other_patient,
+        &lines,
+        &12_500,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx.client.try_submit_claim(
+        &provider,
+        &provider,
+        &patient,
+        &insurer,
+        &vec![
+            &ctx.env,
+            line(&ctx, record_id, hash.clone()),
+            line(&ctx, record_id, hash),
+        ],
+        &12_500,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx
+        .client
+        .try_submit_claim(&insurer, &provider, &patient, &insurer, &lines, &12_500);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    // Only the addressed insurer decides, and only parties read the claim
+    let claim_id = ctx
+        .client
+        .submit_claim(&provider, &provider, &patient, &insurer, &lines, &12_500);
+    let rival = create_test_user(&ctx, Role::Insurer, "Rival");
+    let res = ctx.client.try_verify_claim(&rival, &claim_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_approve_claim(&rival, &claim_id);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_get_claim(&rival, &claim_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_get_claim(&insurer, &99);
+    assert_eq!(res.err(), Some(Ok(ContractError::ClaimNotFound)));
+}
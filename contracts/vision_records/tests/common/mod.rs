@@ -1,4 +1,4 @@
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
 use vision_records::{RecordType, Role, VisionRecordsContract, VisionRecordsContractClient};
 
 pub struct TestContext {
@@ -22,13 +22,30 @@ pub fn setup_test_env() -> TestContext {
 }
 
 /// Creates and registers a user for tests and returns its address.
+/// Clinical providers also get a credential attested by the admin.
 pub fn create_test_user(ctx: &TestContext, role: Role, name: &str) -> Address {
     let user = Address::generate(&ctx.env);
     let name = String::from_str(&ctx.env, name);
     ctx.client.register_user(&ctx.admin, &user, &role, &name);
+    if role == Role::Optometrist || role == Role::Ophthalmologist {
+        attest_test_credential(ctx, &user, role);
+    }
     user
 }
 
+/// Attests a credential for `provider` that stays valid for ten years.
+pub fn attest_test_credential(ctx: &TestContext, provider: &Address, specialty: Role) {
+    let expires_at = ctx.env.ledger().timestamp() + 10 * 365 * 86400;
+    ctx.client.attest_credential(
+        &ctx.admin,
+        provider,
+        &BytesN::from_array(&ctx.env, &[7; 32]),
+        &String::from_str(&ctx.env, "US-CA"),
+        &specialty,
+        &expires_at,
+    );
+}
+
 /// Creates a record and returns the generated record id.
 pub fn create_test_record(
     ctx: &TestContext,
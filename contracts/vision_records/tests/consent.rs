@@ -0,0 +1,168 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env, TestContext};
+use soroban_sdk::{vec, Address, Vec};
+use vision_records::{AccessLevel, ContractError, PurposeOfUse, RecordType, Role};
+
+fn setup_record(ctx: &TestContext) -> (Address, Address, u64) {
+    let patient = create_test_user(ctx, Role::Patient, "Patient");
+    let provider = create_test_user(ctx, Role::Optometrist, "Provider");
+    let record_id = create_test_record(
+        ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    (patient, provider, record_id)
+}
+
+#[test]
+fn test_default_directive_denies_research() {
+    let ctx = setup_test_env();
+    let (patient, provider, record_id) = setup_record(&ctx);
+    let researcher = create_test_user(&ctx, Role::Staff, "Researcher");
+
+    let directive = ctx.client.get_consent_directive(&patient);
+    assert!(directive.denied_purposes.contains(&PurposeOfUse::Research));
+    assert!(directive
+        .default_purposes
+        .contains(&PurposeOfUse::Treatment));
+
+    let res = ctx.client.try_grant_access_for_purposes(
+        &patient,
+        &patient,
+        &researcher,
+        &AccessLevel::Read,
+        &3600,
+        &vec![&ctx.env, PurposeOfUse::Research],
+    );
+    assert_eq!(res, Err(Ok(ContractError::AccessDenied)));
+
+    // The record's own provider cannot declare research either
+    let res = ctx
+        .client
+        .try_access_record(&provider, &record_id, &PurposeOfUse::Research);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+    ctx.client
+        .access_record(&provider, &record_id, &PurposeOfUse::Treatment);
+}
+
+#[test]
+fn test_read_purpose_must_match_grant() {
+    let ctx = setup_test_env();
+    let (patient, _, record_id) = setup_record(&ctx);
+    let researcher = create_test_user(&ctx, Role::Staff, "Researcher");
+
+    ctx.client.set_consent_directive(
+        &patient,
+        &vec![&ctx.env, PurposeOfUse::Treatment],
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.grant_access_for_purposes(
+        &patient,
+        &patient,
+        &researcher,
+        &AccessLevel::Read,
+        &3600,
+        &vec![&ctx.env, PurposeOfUse::Research],
+    );
+    assert_eq!(
+        ctx.client.get_grant_purposes(&patient, &researcher),
+        vec![&ctx.env, PurposeOfUse::Research]
+    );
+
+    ctx.client
+        .access_record(&researcher, &record_id, &PurposeOfUse::Research);
+    let res = ctx
+        .client
+        .try_access_record(&researcher, &record_id, &PurposeOfUse::Payment);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+
+    // Denying research again takes effect for the existing grant at once
+    ctx.client.set_consent_directive(
+        &patient,
+        &vec![&ctx.env, PurposeOfUse::Treatment],
+        &vec![&ctx.env, PurposeOfUse::Research],
+    );
+    let res = ctx
+        .client
+        .try_access_record(&researcher, &record_id, &PurposeOfUse::Research);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+}
+
+#[test]
+fn test_grant_access_uses_directive_defaults() {
+    let ctx = setup_test_env();
+    let (patient, _, _) = setup_record(&ctx);
+    let billing = create_test_user(&ctx, Role::Staff, "Billing");
+
+    ctx.client.set_consent_directive(
+        &patient,
+        &vec![&ctx.env, PurposeOfUse::Payment],
+        &vec![&ctx.env, PurposeOfUse::Research],
+    );
+    ctx.client
+        .grant_access(&patient, &patient, &billing, &AccessLevel::Read, &3600);
+    assert_eq!(
+        ctx.client.get_grant_purposes(&patient, &billing),
+        vec![&ctx.env, PurposeOfUse::Payment]
+    );
+}
+
+#[test]
+fn test_consent_validation() {
+    let ctx = setup_test_env();
+    let (patient, provider, _) = setup_record(&ctx);
+
+    // A purpose cannot be both a default and denied
+    let res = ctx.client.try_set_consent_directive(
+        &patient,
+        &vec![&ctx.env, PurposeOfUse::Treatment],
+        &vec![&ctx.env, PurposeOfUse::Treatment],
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let res = ctx.client.try_grant_access_for_purposes(
+        &patient,
+        &patient,
+        &provider,
+        &AccessLevel::Read,
+        &3600,
+        &Vec::new(&ctx.env),
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_grant_stored_without_purposes_takes_directive_defaults() {
+    use soroban_sdk::symbol_short;
+    use vision_records::AccessGrant;
+
+    let ctx = setup_test_env();
+    let (patient, _, record_id) = setup_record(&ctx);
+    let nurse = create_test_user(&ctx, Role::Staff, "Nurse");
+
+    // A grant written before purposes were tracked has no purposes entry
+    let grant = AccessGrant {
+        patient: patient.clone(),
+        grantee: nurse.clone(),
+        level: AccessLevel::Read,
+        granted_at: 0,
+        expires_at: 3600,
+    };
+    ctx.env.as_contract(&ctx.client.address, || {
+        ctx.env.storage().persistent().set(
+            &(symbol_short!("ACCESS"), patient.clone(), nurse.clone()),
+            &grant,
+        );
+    });
+
+    assert_eq!(
+        ctx.client.get_grant_purposes(&patient, &nurse),
+        ctx.client.get_consent_directive(&patient).default_purposes
+    );
+    ctx.client
+        .access_record(&nurse, &record_id, &PurposeOfUse::Treatment);
+}
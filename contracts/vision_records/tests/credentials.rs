@@ -0,0 +1,168 @@
+mod common;
+
+use common::{attest_test_credential, create_test_record, create_test_user, setup_test_env};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, BytesN, String};
+use vision_records::{ContractError, CredentialStatus, Permission, RecordType, Role};
+
+#[test]
+fn test_clinical_permissions_require_credential() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+
+    // Register a provider without the test helper's automatic credential
+    let provider = Address::generate(&ctx.env);
+    ctx.client.register_user(
+        &ctx.admin,
+        &provider,
+        &Role::Ophthalmologist,
+        &String::from_str(&ctx.env, "Dr. Unverified"),
+    );
+
+    assert!(!ctx
+        .client
+        .check_permission(&provider, &Permission::WriteRecord));
+    assert!(!ctx
+        .client
+        .check_permission(&provider, &Permission::ReadAnyRecord));
+    // Non-clinical permissions of the role are unaffected
+    assert!(ctx
+        .client
+        .check_permission(&provider, &Permission::ManageUsers));
+
+    let hash = String::from_str(&ctx.env, "QmHash");
+    let res = ctx.client.try_add_record(
+        &provider,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &hash,
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    attest_test_credential(&ctx, &provider, Role::Ophthalmologist);
+    assert!(ctx
+        .client
+        .has_valid_credential(&provider, &Role::Ophthalmologist));
+    assert!(ctx
+        .client
+        .check_permission(&provider, &Permission::WriteRecord));
+    create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmHash",
+    );
+}
+
+#[test]
+fn test_credential_must_match_role_and_expire() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+
+    let provider = Address::generate(&ctx.env);
+    ctx.client.register_user(
+        &ctx.admin,
+        &provider,
+        &Role::Ophthalmologist,
+        &String::from_str(&ctx.env, "Dr. Opto"),
+    );
+
+    // An optometry license does not qualify an ophthalmologist
+    attest_test_credential(&ctx, &provider, Role::Optometrist);
+    assert!(!ctx
+        .client
+        .check_permission(&provider, &Permission::WriteRecord));
+
+    ctx.client.attest_credential(
+        &ctx.admin,
+        &provider,
+        &BytesN::from_array(&ctx.env, &[1; 32]),
+        &String::from_str(&ctx.env, "NG-LA"),
+        &Role::Ophthalmologist,
+        &2_000,
+    );
+    let credential = ctx.client.get_credential(&provider);
+    assert_eq!(credential.verifier, ctx.admin);
+    assert_eq!(credential.status, CredentialStatus::Active);
+    assert!(ctx
+        .client
+        .check_permission(&provider, &Permission::WriteRecord));
+
+    ctx.env.ledger().set_timestamp(2_000);
+    assert!(!ctx
+        .client
+        .check_permission(&provider, &Permission::WriteRecord));
+}
+
+#[test]
+fn test_suspend_and_reinstate_credential() {
+    let ctx = setup_test_env();
+    let provider = create_test_user(&ctx, Role::Optometrist, "Opto");
+    assert!(ctx
+        .client
+        .check_permission(&provider, &Permission::WriteRecord));
+
+    ctx.client.suspend_credential(&ctx.admin, &provider);
+    assert_eq!(
+        ctx.client.get_credential(&provider).status,
+        CredentialStatus::Suspended
+    );
+    assert!(!ctx
+        .client
+        .check_permission(&provider, &Permission::WriteRecord));
+
+    ctx.client.reinstate_credential(&ctx.admin, &provider);
+    assert!(ctx
+        .client
+        .check_permission(&provider, &Permission::WriteRecord));
+
+    let stranger = Address::generate(&ctx.env);
+    let res = ctx.client.try_suspend_credential(&ctx.admin, &stranger);
+    assert_eq!(res, Err(Ok(ContractError::CredentialNotFound)));
+}
+
+#[test]
+fn test_only_accredited_verifiers_attest() {
+    let ctx = setup_test_env();
+    let verifier = create_test_user(&ctx, Role::Staff, "Licensing Board");
+    let provider = Address::generate(&ctx.env);
+    let hash = BytesN::from_array(&ctx.env, &[3; 32]);
+    let jurisdiction = String::from_str(&ctx.env, "UK");
+
+    let res = ctx.client.try_attest_credential(
+        &verifier,
+        &provider,
+        &hash,
+        &jurisdiction,
+        &Role::Optometrist,
+        &86400,
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    // Accreditation is granted by an admin as a custom permission
+    ctx.client
+        .grant_custom_permission(&ctx.admin, &verifier, &Permission::VerifyCredentials);
+    ctx.client.attest_credential(
+        &verifier,
+        &provider,
+        &hash,
+        &jurisdiction,
+        &Role::Optometrist,
+        &86400,
+    );
+    assert_eq!(ctx.client.get_credential(&provider).verifier, verifier);
+
+    // Credentials only exist for clinical specialties
+    let res = ctx.client.try_attest_credential(
+        &verifier,
+        &provider,
+        &hash,
+        &jurisdiction,
+        &Role::Staff,
+        &86400,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
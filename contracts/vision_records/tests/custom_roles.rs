@@ -0,0 +1,182 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env, TestContext};
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{vec, String};
+use vision_records::{AccessLevel, ContractError, Permission, RecordType, Role};
+
+fn define_role(ctx: &TestContext, name: &str, permissions: &[Permission]) -> u64 {
+    let mut perms = soroban_sdk::Vec::new(&ctx.env);
+    for permission in permissions {
+        perms.push_back(permission.clone());
+    }
+    ctx.client
+        .create_custom_role(&ctx.admin, &String::from_str(&ctx.env, name), &perms)
+}
+
+#[test]
+fn test_custom_role_assignment_and_expiry() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let scheduler = create_test_user(&ctx, Role::Staff, "Scheduler");
+    let role_id = define_role(&ctx, "Front Desk Scheduler", &[Permission::ManageAccess]);
+    assert_eq!(
+        ctx.client.get_custom_role(&role_id).name,
+        String::from_str(&ctx.env, "Front Desk Scheduler")
+    );
+
+    ctx.client
+        .assign_custom_role(&ctx.admin, &scheduler, &role_id, &2_000);
+    assert!(ctx
+        .client
+        .check_permission(&scheduler, &Permission::ManageAccess));
+    // The enum role keeps working alongside it
+    assert!(ctx
+        .client
+        .check_permission(&scheduler, &Permission::ManageUsers));
+
+    ctx.env.ledger().set_timestamp(2_000);
+    assert!(!ctx
+        .client
+        .check_permission(&scheduler, &Permission::ManageAccess));
+
+    // Editing the role applies to every holder
+    ctx.client
+        .assign_custom_role(&ctx.admin, &scheduler, &role_id, &0);
+    ctx.client.set_custom_role_permissions(
+        &ctx.admin,
+        &role_id,
+        &vec![&ctx.env, Permission::ReadAuditLog],
+    );
+    assert!(!ctx
+        .client
+        .check_permission(&scheduler, &Permission::ManageAccess));
+    assert!(ctx
+        .client
+        .check_permission(&scheduler, &Permission::ReadAuditLog));
+
+    ctx.client.revoke_custom_role(&ctx.admin, &scheduler);
+    assert!(!ctx
+        .client
+        .check_permission(&scheduler, &Permission::ReadAuditLog));
+}
+
+#[test]
+fn test_custom_role_delegation() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let caregiver = create_test_user(&ctx, Role::Patient, "Caregiver");
+    let doctor = create_test_user(&ctx, Role::Optometrist, "Doctor");
+    let role_id = define_role(&ctx, "Access Manager", &[Permission::ManageAccess]);
+
+    // The role can only be delegated by someone holding it
+    let res = ctx
+        .client
+        .try_delegate_custom_role(&patient, &caregiver, &role_id, &0);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    ctx.client
+        .assign_custom_role(&ctx.admin, &patient, &role_id, &0);
+    ctx.client
+        .delegate_custom_role(&patient, &caregiver, &role_id, &0);
+    ctx.client
+        .grant_access(&caregiver, &patient, &doctor, &AccessLevel::Read, &3600);
+    assert_eq!(
+        ctx.client.check_access(&patient, &doctor),
+        AccessLevel::Read
+    );
+
+    // Losing the role ends the delegation too
+    ctx.client.revoke_custom_role(&ctx.admin, &patient);
+    let res =
+        ctx.client
+            .try_grant_access(&caregiver, &patient, &doctor, &AccessLevel::Write, &3600);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_clinical_custom_roles_need_credentials() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let lab_tech = create_test_user(&ctx, Role::Staff, "Lab Tech");
+    let optometrist = create_test_user(&ctx, Role::Optometrist, "Opto");
+    let role_id = define_role(&ctx, "Optical Lab Tech", &[Permission::WriteRecord]);
+
+    ctx.client
+        .assign_custom_role(&ctx.admin, &lab_tech, &role_id, &0);
+    assert!(!ctx
+        .client
+        .check_permission(&lab_tech, &Permission::WriteRecord));
+
+    // Any valid credential qualifies the holder of a custom clinical role, even
+    // once the credentialed optometrist is re-registered as staff
+    ctx.client.register_user(
+        &ctx.admin,
+        &optometrist,
+        &Role::Staff,
+        &String::from_str(&ctx.env, "Opto"),
+    );
+    ctx.client
+        .assign_custom_role(&ctx.admin, &optometrist, &role_id, &0);
+    create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::LabResult,
+        "QmLab",
+    );
+}
+
+#[test]
+fn test_custom_role_validation() {
+    let ctx = setup_test_env();
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+    let perms = vec![&ctx.env, Permission::ManageAccess];
+
+    let res =
+        ctx.client
+            .try_create_custom_role(&staff, &String::from_str(&ctx.env, "Rogue"), &perms);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res =
+        ctx.client
+            .try_create_custom_role(&ctx.admin, &String::from_str(&ctx.env, ""), &perms);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let res = ctx
+        .client
+        .try_assign_custom_role(&ctx.admin, &staff, &42, &0);
+    assert_eq!(res, Err(Ok(ContractError::CustomRoleNotFound)));
+    let res = ctx.client.try_revoke_custom_role(&ctx.admin, &staff);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_privileged_custom_roles_need_system_admin() {
+    let ctx = setup_test_env();
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+    let admin_role = define_role(
+        &ctx,
+        "Shadow Admin",
+        &[Permission::SystemAdmin, Permission::SystemAdmin],
+    );
+    assert_eq!(ctx.client.get_custom_role(&admin_role).permissions.len(), 1);
+
+    // Staff hold ManageUsers but cannot hand out, or take, a privileged role
+    let res = ctx
+        .client
+        .try_assign_custom_role(&staff, &staff, &admin_role, &0);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    assert!(!ctx
+        .client
+        .check_permission(&staff, &Permission::SystemAdmin));
+
+    let desk_role = define_role(&ctx, "Front Desk", &[Permission::ManageAppointments]);
+    ctx.client
+        .assign_custom_role(&staff, &staff, &desk_role, &0);
+    ctx.client
+        .assign_custom_role(&ctx.admin, &staff, &admin_role, &0);
+    assert!(ctx
+        .client
+        .check_permission(&staff, &Permission::SystemAdmin));
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::Address;
+use vision_records::{ContractError, EscrowStatus, RecordType, Role};
+
+#[test]
+fn test_matching_record_releases_payment() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let token_id = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env))
+        .address();
+    StellarAssetClient::new(&ctx.env, &token_id).mint(&patient, &1_000);
+    let token = TokenClient::new(&ctx.env, &token_id);
+
+    let escrow_id = ctx.client.create_escrow(
+        &patient,
+        &provider,
+        &token_id,
+        &400,
+        &RecordType::Examination,
+        &86_400,
+    );
+    assert_eq!(token.balance(&patient), 600);
+    assert_eq!(token.balance(&ctx.client.address), 400);
+    assert_eq!(ctx.client.get_provider_escrows(&provider).len(), 1);
+
+    // Only a record of the paid-for type settles it
+    create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Diagnosis,
+        "QmDiag",
+    );
+    assert_eq!(ctx.client.get_escrow(&escrow_id).status, EscrowStatus::Held);
+
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let escrow = ctx.client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Matched);
+    assert_eq!(escrow.record_id, record_id);
+    assert_eq!(token.balance(&provider), 0);
+
+    // The payout is a separate call, and a matched escrow is never refunded
+    ctx.env.ledger().set_timestamp(1_000 + 86_400);
+    let res = ctx.client.try_refund_escrow(&patient, &escrow_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    ctx.client.release_escrow(&provider, &escrow_id);
+    assert_eq!(
+        ctx.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    let res = ctx.client.try_release_escrow(&provider, &escrow_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(token.balance(&provider), 400);
+    assert_eq!(token.balance(&ctx.client.address), 0);
+}
+
+#[test]
+fn test_refund_after_timeout() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let token_id = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env))
+        .address();
+    StellarAssetClient::new(&ctx.env, &token_id).mint(&patient, &1_000);
+    let token = TokenClient::new(&ctx.env, &token_id);
+    let escrow_id = ctx.client.create_escrow(
+        &patient,
+        &provider,
+        &token_id,
+        &400,
+        &RecordType::Examination,
+        &86_400,
+    );
+
+    let res = ctx.client.try_refund_escrow(&patient, &escrow_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx.client.try_release_escrow(&provider, &escrow_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // Records added after the deadline no longer release the payment
+    ctx.env.ledger().set_timestamp(1_000 + 86_400);
+    create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    assert_eq!(token.balance(&provider), 0);
+
+    ctx.client.refund_escrow(&provider, &escrow_id);
+    assert_eq!(
+        ctx.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(token.balance(&patient), 1_000);
+    let res = ctx.client.try_refund_escrow(&patient, &escrow_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_escrow_validation() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let token_id = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env))
+        .address();
+    StellarAssetClient::new(&ctx.env, &token_id).mint(&patient, &1_000);
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+
+    let res = ctx.client.try_create_escrow(
+        &patient,
+        &staff,
+        &token_id,
+        &400,
+        &RecordType::Examination,
+        &86_400,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx.client.try_create_escrow(
+        &patient,
+        &provider,
+        &token_id,
+        &0,
+        &RecordType::Examination,
+        &86_400,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx.client.try_create_escrow(
+        &patient,
+        &provider,
+        &token_id,
+        &400,
+        &RecordType::Examination,
+        &0,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    // Overlong timeouts are refused rather than overflowing the deadline
+    for timeout in [366 * 86_400, u64::MAX] {
+        let res = ctx.client.try_create_escrow(
+            &patient,
+            &provider,
+            &token_id,
+            &400,
+            &RecordType::Examination,
+            &timeout,
+        );
+        assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    }
+    assert_eq!(ctx.client.get_patient_escrows(&patient).len(), 0);
+
+    let res = ctx.client.try_get_escrow(&99);
+    assert_eq!(res.err(), Some(Ok(ContractError::EscrowNotFound)));
+}
@@ -0,0 +1,179 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env, TestContext};
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::{vec, Address, String, Vec};
+use vision_records::{
+    ContractError, ExamMetric, EyeExamination, RecordType, Role, VisualAcuity, VisualField,
+    VisualFieldIndices,
+};
+
+fn eye(iop: u32, mean_deviation: Option<i32>) -> EyeExamination {
+    EyeExamination {
+        acuity: VisualAcuity::Snellen(20, 25),
+        intraocular_pressure: iop,
+        visual_field: match mean_deviation {
+            Some(md) => VisualField::Performed(VisualFieldIndices {
+                mean_deviation: md,
+                pattern_std_deviation: 180,
+                visual_field_index: 9800,
+            }),
+            None => VisualField::NotPerformed,
+        },
+    }
+}
+
+fn exam_record(ctx: &TestContext, patient: &Address, provider: &Address, hash: &str) -> u64 {
+    create_test_record(
+        ctx,
+        provider,
+        patient,
+        provider,
+        RecordType::Examination,
+        hash,
+    )
+}
+
+#[test]
+fn test_add_and_get_examination() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Ophthalmologist, "Provider");
+    let record_id = exam_record(&ctx, &patient, &provider, "QmExam");
+
+    let imaging = vec![&ctx.env, String::from_str(&ctx.env, "QmFundusOD")];
+    let left = EyeExamination {
+        acuity: VisualAcuity::LogMar(100),
+        ..eye(155, Some(-250))
+    };
+    ctx.client
+        .add_examination(&provider, &record_id, &left, &eye(162, None), &imaging);
+
+    let exam = ctx.client.get_examination(&record_id);
+    assert_eq!(exam.patient, patient);
+    assert_eq!(exam.left_eye.acuity, VisualAcuity::LogMar(100));
+    assert_eq!(exam.right_eye.intraocular_pressure, 162);
+    assert_eq!(exam.right_eye.visual_field, VisualField::NotPerformed);
+    assert_eq!(exam.imaging_refs.len(), 1);
+    assert_eq!(ctx.client.get_patient_examinations(&patient).len(), 1);
+
+    // Results can only be attached once per record
+    let res = ctx.client.try_add_examination(
+        &provider,
+        &record_id,
+        &eye(150, None),
+        &eye(150, None),
+        &Vec::new(&ctx.env),
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_add_examination_validation() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+    let record_id = exam_record(&ctx, &patient, &provider, "QmExam");
+    let rx_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Prescription,
+        "QmRx",
+    );
+    let none = Vec::new(&ctx.env);
+
+    let res =
+        ctx.client
+            .try_add_examination(&provider, &rx_id, &eye(150, None), &eye(150, None), &none);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let bad_acuity = EyeExamination {
+        acuity: VisualAcuity::Snellen(20, 0),
+        ..eye(150, None)
+    };
+    let res =
+        ctx.client
+            .try_add_examination(&provider, &record_id, &bad_acuity, &eye(150, None), &none);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let res = ctx.client.try_add_examination(
+        &provider,
+        &record_id,
+        &eye(801, None),
+        &eye(150, None),
+        &none,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let res =
+        ctx.client
+            .try_add_examination(&staff, &record_id, &eye(150, None), &eye(150, None), &none);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    let res = ctx.client.try_get_examination(&record_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::ExaminationNotFound)));
+}
+
+#[test]
+fn test_exam_trends() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Ophthalmologist, "Provider");
+    let none = Vec::new(&ctx.env);
+
+    let readings = [
+        (1_000, 180, Some(-100)),
+        (2_000, 210, None),
+        (3_000, 165, Some(-180)),
+    ];
+    for (i, (time, iop, md)) in readings.iter().enumerate() {
+        ctx.env.ledger().set_timestamp(*time);
+        let hash = ["QmA", "QmB", "QmC"][i];
+        let record_id = exam_record(&ctx, &patient, &provider, hash);
+        ctx.client.add_examination(
+            &provider,
+            &record_id,
+            &eye(*iop, *md),
+            &eye(*iop, *md),
+            &none,
+        );
+    }
+
+    let iop = ctx
+        .client
+        .get_exam_trend(&patient, &ExamMetric::IntraocularPressure);
+    assert_eq!(iop.len(), 3);
+    assert_eq!(iop.get(0).unwrap().left, Some(180));
+    assert_eq!(iop.get(1).unwrap().right, Some(210));
+    assert_eq!(iop.get(2).unwrap().recorded_at, 3_000);
+
+    // The exam without perimetry is left out of the visual field series
+    let md = ctx
+        .client
+        .get_exam_trend(&patient, &ExamMetric::MeanDeviation);
+    assert_eq!(md.len(), 2);
+    assert_eq!(md.get(1).unwrap().left, Some(-180));
+
+    // A one-eye reading is kept, with the unmeasured eye left empty
+    ctx.env.ledger().set_timestamp(4_000);
+    let record_id = exam_record(&ctx, &patient, &provider, "QmD");
+    ctx.client
+        .add_examination(&provider, &record_id, &eye(190, None), &eye(0, None), &none);
+    let iop = ctx
+        .client
+        .get_exam_trend(&patient, &ExamMetric::IntraocularPressure);
+    assert_eq!(iop.len(), 4);
+    assert_eq!(iop.get(3).unwrap().left, Some(190));
+    assert_eq!(iop.get(3).unwrap().right, None);
+
+    let other = create_test_user(&ctx, Role::Patient, "Other");
+    assert_eq!(
+        ctx.client
+            .get_exam_trend(&other, &ExamMetric::IntraocularPressure)
+            .len(),
+        0
+    );
+}
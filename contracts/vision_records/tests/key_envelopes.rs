@@ -0,0 +1,357 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{map, vec, Address, Bytes, String};
+use vision_records::{AccessLevel, ContractError, GrantTarget, RecordType, Role};
+
+#[test]
+fn test_envelopes_are_served_to_their_grantee() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let grantee = create_test_user(&ctx, Role::Staff, "Grantee");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    ctx.client
+        .set_encryption_key(&grantee, &Bytes::from_array(&ctx.env, &[1; 32]));
+    let wrapped = Bytes::from_array(&ctx.env, &[9; 48]);
+
+    ctx.client.grant_access_with_keys(
+        &patient,
+        &patient,
+        &grantee,
+        &AccessLevel::Read,
+        &3600,
+        &map![&ctx.env, (record_id, wrapped.clone())],
+    );
+    assert_eq!(
+        ctx.client.get_encryption_key(&grantee),
+        Some(Bytes::from_array(&ctx.env, &[1; 32]))
+    );
+    let envelope = ctx.client.get_key_envelope(&grantee, &record_id);
+    assert_eq!(envelope.wrapped_key, wrapped);
+    assert!(!envelope.stale);
+
+    // Access without an envelope of one's own yields nothing
+    let other = create_test_user(&ctx, Role::Staff, "Other");
+    ctx.client
+        .grant_access(&patient, &patient, &other, &AccessLevel::Read, &3600);
+    let res = ctx.client.try_get_key_envelope(&other, &record_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::KeyNotFound)));
+
+    let outsider = create_test_user(&ctx, Role::Staff, "Outsider");
+    let res = ctx.client.try_get_key_envelope(&outsider, &record_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+}
+
+#[test]
+fn test_revocation_marks_envelopes_stale() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let grantee = create_test_user(&ctx, Role::Staff, "Grantee");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    ctx.client
+        .set_encryption_key(&grantee, &Bytes::from_array(&ctx.env, &[1; 32]));
+
+    ctx.client.grant_access_with_keys(
+        &patient,
+        &patient,
+        &grantee,
+        &AccessLevel::Read,
+        &3600,
+        &map![&ctx.env, (record_id, Bytes::from_array(&ctx.env, &[9; 48]))],
+    );
+    ctx.client.revoke_access(&patient, &grantee);
+    let res = ctx.client.try_get_key_envelope(&grantee, &record_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+
+    // A new grant does not revive the old envelope...
+    ctx.client
+        .grant_access(&patient, &patient, &grantee, &AccessLevel::Read, &3600);
+    let res = ctx.client.try_get_key_envelope(&grantee, &record_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::KeyNotFound)));
+
+    // ...until a fresh one is posted
+    let fresh = Bytes::from_array(&ctx.env, &[3; 48]);
+    ctx.client.put_key_envelopes(
+        &patient,
+        &patient,
+        &grantee,
+        &map![&ctx.env, (record_id, fresh.clone())],
+    );
+    assert_eq!(
+        ctx.client
+            .get_key_envelope(&grantee, &record_id)
+            .wrapped_key,
+        fresh
+    );
+}
+
+#[test]
+fn test_envelope_validation() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let grantee = create_test_user(&ctx, Role::Staff, "Grantee");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    ctx.client
+        .set_encryption_key(&grantee, &Bytes::from_array(&ctx.env, &[1; 32]));
+    let keyless = create_test_user(&ctx, Role::Staff, "Keyless");
+    let envelopes = map![&ctx.env, (record_id, Bytes::from_array(&ctx.env, &[9; 48]))];
+
+    // Without a registered key the whole grant is rejected
+    let res = ctx.client.try_grant_access_with_keys(
+        &patient,
+        &patient,
+        &keyless,
+        &AccessLevel::Read,
+        &3600,
+        &envelopes,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(
+        ctx.client.check_access(&patient, &keyless),
+        AccessLevel::None
+    );
+
+    // Envelopes only cover the patient's own records, for current grantees
+    let other_patient = create_test_user(&ctx, Role::Patient, "Other Patient");
+    let res = ctx.client.try_grant_access_with_keys(
+        &other_patient,
+        &other_patient,
+        &grantee,
+        &AccessLevel::Read,
+        &3600,
+        &envelopes,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx
+        .client
+        .try_put_key_envelopes(&patient, &patient, &grantee, &envelopes);
+    assert_eq!(res, Err(Ok(ContractError::AccessDenied)));
+
+    let res = ctx.client.try_set_encryption_key(
+        &Address::generate(&ctx.env),
+        &Bytes::from_array(&ctx.env, &[1; 32]),
+    );
+    assert_eq!(res, Err(Ok(ContractError::UserNotFound)));
+}
+
+#[test]
+fn test_revocation_queues_records_for_rotation() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let grantee = create_test_user(&ctx, Role::Staff, "Grantee");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    ctx.client
+        .set_encryption_key(&grantee, &Bytes::from_array(&ctx.env, &[1; 32]));
+    let record = ctx.client.get_record(&record_id);
+    let second_id = create_test_record(
+        &ctx,
+        &record.provider,
+        &patient,
+        &record.provider,
+        RecordType::Diagnosis,
+        "QmDiag",
+    );
+    let revoked = create_test_user(&ctx, Role::Staff, "Revoked");
+    ctx.client
+        .set_encryption_key(&revoked, &Bytes::from_array(&ctx.env, &[2; 32]));
+    for grantee in [&grantee, &revoked] {
+        ctx.client.grant_access_with_keys(
+            &patient,
+            &patient,
+            grantee,
+            &AccessLevel::Read,
+            &3600,
+            &map![&ctx.env, (record_id, Bytes::from_array(&ctx.env, &[9; 48]))],
+        );
+    }
+
+    ctx.client.revoke_access(&patient, &revoked);
+    assert_eq!(ctx.client.get_key_epoch(&patient), 1);
+    assert_eq!(
+        ctx.client.get_pending_rotations(&patient),
+        vec![&ctx.env, record_id, second_id]
+    );
+
+    // Only the record's provider re-encrypts, and only for current grantees
+    let fresh = Bytes::from_array(&ctx.env, &[4; 48]);
+    let new_hash = String::from_str(&ctx.env, "QmExamV2");
+    let res = ctx.client.try_rotate_record_key(
+        &record.provider,
+        &record_id,
+        &new_hash,
+        &map![&ctx.env, (revoked.clone(), fresh.clone())],
+    );
+    assert_eq!(res, Err(Ok(ContractError::AccessDenied)));
+    let other_provider = create_test_user(&ctx, Role::Optometrist, "Other Provider");
+    let res = ctx.client.try_rotate_record_key(
+        &other_provider,
+        &record_id,
+        &new_hash,
+        &map![&ctx.env, (grantee.clone(), fresh.clone())],
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    ctx.client.rotate_record_key(
+        &record.provider,
+        &record_id,
+        &new_hash,
+        &map![&ctx.env, (grantee.clone(), fresh.clone())],
+    );
+    assert_eq!(ctx.client.get_record(&record_id).data_hash, new_hash);
+    assert_eq!(ctx.client.get_record_key_epoch(&record_id), 1);
+    assert_eq!(
+        ctx.client.get_pending_rotations(&patient),
+        vec![&ctx.env, second_id]
+    );
+    let envelope = ctx.client.get_key_envelope(&grantee, &record_id);
+    assert_eq!((envelope.wrapped_key, envelope.epoch), (fresh, 1));
+
+    // Records added afterwards start under the new epoch
+    let third_id = create_test_record(
+        &ctx,
+        &record.provider,
+        &patient,
+        &record.provider,
+        RecordType::LabResult,
+        "QmLab",
+    );
+    assert_eq!(ctx.client.get_record_key_epoch(&third_id), 1);
+    let res =
+        ctx.client
+            .try_rotate_record_key(&record.provider, &third_id, &new_hash, &map![&ctx.env]);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_envelopes_from_before_rotation_are_not_served() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let grantee = create_test_user(&ctx, Role::Staff, "Grantee");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    ctx.client
+        .set_encryption_key(&grantee, &Bytes::from_array(&ctx.env, &[1; 32]));
+    let provider = ctx.client.get_record(&record_id).provider;
+    let revoked = create_test_user(&ctx, Role::Staff, "Revoked");
+
+    ctx.client.grant_access_with_keys(
+        &patient,
+        &patient,
+        &grantee,
+        &AccessLevel::Read,
+        &3600,
+        &map![&ctx.env, (record_id, Bytes::from_array(&ctx.env, &[9; 48]))],
+    );
+    ctx.client
+        .grant_access(&patient, &patient, &revoked, &AccessLevel::Read, &3600);
+    ctx.client.revoke_access(&patient, &revoked);
+
+    // Until the record is rotated, the old key still decrypts it
+    ctx.client.get_key_envelope(&grantee, &record_id);
+
+    ctx.client.rotate_record_key(
+        &provider,
+        &record_id,
+        &String::from_str(&ctx.env, "QmExamV2"),
+        &map![&ctx.env],
+    );
+    let res = ctx.client.try_get_key_envelope(&grantee, &record_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::KeyNotFound)));
+}
+
+#[test]
+fn test_group_changes_rotate_keys() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let grantee = create_test_user(&ctx, Role::Staff, "Grantee");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    ctx.client
+        .set_encryption_key(&grantee, &Bytes::from_array(&ctx.env, &[1; 32]));
+    let team_id = ctx
+        .client
+        .create_care_team(&patient, &String::from_str(&ctx.env, "Clinic"));
+    ctx.client
+        .add_care_team_member(&patient, &team_id, &grantee);
+    let team = GrantTarget::CareTeam(team_id);
+
+    // Leaving a team without a grant changes nothing
+    ctx.client
+        .remove_care_team_member(&patient, &team_id, &grantee);
+    assert_eq!(ctx.client.get_key_epoch(&patient), 0);
+
+    ctx.client
+        .add_care_team_member(&patient, &team_id, &grantee);
+    ctx.client
+        .grant_group_access(&patient, &patient, &team, &AccessLevel::Read, &3600);
+    ctx.client.grant_access_with_keys(
+        &patient,
+        &patient,
+        &grantee,
+        &AccessLevel::Read,
+        &3600,
+        &map![&ctx.env, (record_id, Bytes::from_array(&ctx.env, &[9; 48]))],
+    );
+
+    // A member leaving a team with a grant loses their envelopes
+    ctx.client
+        .remove_care_team_member(&patient, &team_id, &grantee);
+    assert_eq!(ctx.client.get_key_epoch(&patient), 1);
+    let res = ctx.client.try_get_key_envelope(&grantee, &record_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::KeyNotFound)));
+
+    // Revoking the group grant itself rotates too
+    ctx.client.revoke_group_access(&patient, &team);
+    assert_eq!(ctx.client.get_key_epoch(&patient), 2);
+    assert_eq!(
+        ctx.client.get_pending_rotations(&patient),
+        vec![&ctx.env, record_id]
+    );
+}
@@ -0,0 +1,236 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env};
+use soroban_sdk::String;
+use vision_records::{ContractError, LinkType, RecordType, Role};
+
+#[test]
+fn test_link_prescription_to_examination() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+
+    let exam = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let rx = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Prescription,
+        "QmRx",
+    );
+
+    ctx.client
+        .link_records(&provider, &rx, &exam, &LinkType::DerivedFrom);
+
+    let rx_links = ctx.client.get_record_links(&rx);
+    assert_eq!(rx_links.len(), 1);
+    let link = rx_links.get(0).unwrap();
+    assert_eq!(link.source_id, rx);
+    assert_eq!(link.target_id, exam);
+    assert_eq!(link.link_type, LinkType::DerivedFrom);
+    assert_eq!(ctx.client.get_record_links(&exam).len(), 1);
+
+    // Linking the same pair twice is rejected
+    let dup = ctx
+        .client
+        .try_link_records(&provider, &rx, &exam, &LinkType::DerivedFrom);
+    assert_eq!(dup, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_link_rejects_invalid_combinations() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let other_patient = create_test_user(&ctx, Role::Patient, "Other");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let stranger = create_test_user(&ctx, Role::Staff, "Stranger");
+
+    let exam = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let treatment = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Treatment,
+        "QmTreat",
+    );
+    let other_rx = create_test_record(
+        &ctx,
+        &provider,
+        &other_patient,
+        &provider,
+        RecordType::Prescription,
+        "QmOtherRx",
+    );
+
+    // Treatment must follow a Diagnosis, not an Examination
+    let res = ctx
+        .client
+        .try_link_records(&provider, &treatment, &exam, &LinkType::Follows);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // Records of different patients cannot be linked
+    let res = ctx
+        .client
+        .try_link_records(&provider, &other_rx, &exam, &LinkType::DerivedFrom);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // Only providers able to write for the source record may link it
+    let diagnosis = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Diagnosis,
+        "QmDiag",
+    );
+    let res = ctx
+        .client
+        .try_link_records(&stranger, &treatment, &diagnosis, &LinkType::Follows);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_linked_graph_spans_care_pathway() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let surgeon = create_test_user(&ctx, Role::Ophthalmologist, "Surgeon");
+
+    let diagnosis = create_test_record(
+        &ctx,
+        &surgeon,
+        &patient,
+        &surgeon,
+        RecordType::Diagnosis,
+        "QmDiag",
+    );
+    let treatment = create_test_record(
+        &ctx,
+        &surgeon,
+        &patient,
+        &surgeon,
+        RecordType::Treatment,
+        "QmTreat",
+    );
+    let surgery = create_test_record(
+        &ctx,
+        &surgeon,
+        &patient,
+        &surgeon,
+        RecordType::Surgery,
+        "QmSurgery",
+    );
+    let follow_up = create_test_record(
+        &ctx,
+        &surgeon,
+        &patient,
+        &surgeon,
+        RecordType::Examination,
+        "QmFollowUp",
+    );
+    let unrelated = create_test_record(
+        &ctx,
+        &surgeon,
+        &patient,
+        &surgeon,
+        RecordType::LabResult,
+        "QmLab",
+    );
+
+    ctx.client
+        .link_records(&surgeon, &treatment, &diagnosis, &LinkType::Follows);
+    ctx.client
+        .link_records(&surgeon, &follow_up, &surgery, &LinkType::FollowUpOf);
+
+    let graph = ctx.client.get_linked_graph(&diagnosis);
+    assert_eq!(graph.record_ids.len(), 2);
+    assert!(graph.record_ids.contains(treatment));
+    assert_eq!(graph.links.len(), 1);
+
+    let graph = ctx.client.get_linked_graph(&follow_up);
+    assert_eq!(graph.record_ids.len(), 2);
+    assert!(graph.record_ids.contains(surgery));
+    assert!(!graph.record_ids.contains(unrelated));
+
+    let res = ctx.client.try_get_linked_graph(&999);
+    assert_eq!(res.err(), Some(Ok(ContractError::RecordNotFound)));
+}
+
+#[test]
+fn test_episode_grouping() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let other_patient = create_test_user(&ctx, Role::Patient, "Other");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+
+    let exam = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let rx = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Prescription,
+        "QmRx",
+    );
+    let other_record = create_test_record(
+        &ctx,
+        &provider,
+        &other_patient,
+        &provider,
+        RecordType::Examination,
+        "QmOther",
+    );
+
+    let title = String::from_str(&ctx.env, "Annual eye exam 2026");
+    let episode_id = ctx.client.create_episode(&provider, &patient, &title);
+    assert_eq!(episode_id, 1);
+
+    ctx.client
+        .add_record_to_episode(&provider, &episode_id, &exam);
+    ctx.client
+        .add_record_to_episode(&provider, &episode_id, &rx);
+
+    let episode = ctx.client.get_episode(&episode_id);
+    assert_eq!(episode.patient, patient);
+    assert_eq!(episode.record_ids.len(), 2);
+    assert_eq!(ctx.client.get_record_episode(&rx), Some(episode_id));
+    assert_eq!(ctx.client.get_patient_episodes(&patient).len(), 1);
+
+    // A record belongs to at most one episode
+    let second = ctx.client.create_episode(&provider, &patient, &title);
+    let res = ctx
+        .client
+        .try_add_record_to_episode(&provider, &second, &exam);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // Another patient's record cannot join the episode
+    let res = ctx
+        .client
+        .try_add_record_to_episode(&provider, &episode_id, &other_record);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let res = ctx.client.try_get_episode(&42);
+    assert_eq!(res.err(), Some(Ok(ContractError::EpisodeNotFound)));
+}
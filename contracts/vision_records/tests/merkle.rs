@@ -0,0 +1,183 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env, TestContext};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{map, symbol_short, Address, Bytes, BytesN, String};
+use vision_records::{AccessLevel, ContractError, RecordLeaf, RecordType, Role};
+
+fn leaf_for(ctx: &TestContext, record_id: u64) -> RecordLeaf {
+    let record = ctx.client.get_record(&record_id);
+    RecordLeaf {
+        record_id,
+        data_hash: record.data_hash,
+        record_type: record.record_type,
+        created_at: record.created_at,
+    }
+}
+
+fn add_records(ctx: &TestContext, patient: &Address, provider: &Address, count: u32) -> Vec<u64> {
+    let mut ids = Vec::new();
+    for i in 0..count {
+        let hash = if i % 2 == 0 { "QmEven" } else { "QmOdd" };
+        ids.push(create_test_record(
+            ctx,
+            provider,
+            patient,
+            provider,
+            RecordType::Examination,
+            hash,
+        ));
+    }
+    ids
+}
+
+#[test]
+fn test_every_record_proves_against_the_root() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    assert_eq!(
+        ctx.client.get_merkle_root(&patient),
+        BytesN::from_array(&ctx.env, &[0; 32])
+    );
+
+    // A single leaf is its own root: sha256(0x00 ‖ XDR of the committed tuple)
+    let first = add_records(&ctx, &patient, &provider, 1)[0];
+    let leaf = leaf_for(&ctx, first);
+    let mut preimage = Bytes::from_array(&ctx.env, &[0]);
+    preimage.append(
+        &(
+            leaf.record_id,
+            leaf.data_hash.clone(),
+            leaf.record_type.clone(),
+            leaf.created_at,
+        )
+            .to_xdr(&ctx.env),
+    );
+    let expected: BytesN<32> = ctx.env.crypto().sha256(&preimage).into();
+    assert_eq!(ctx.client.get_merkle_root(&patient), expected);
+
+    // Five leaves give an unbalanced tree of depth three
+    let mut ids = vec![first];
+    ids.extend(add_records(&ctx, &patient, &provider, 4));
+    for record_id in ids {
+        let proof = ctx.client.get_merkle_proof(&record_id);
+        assert_eq!(proof.siblings.len(), 3);
+        assert!(ctx
+            .client
+            .verify_record_inclusion(&patient, &leaf_for(&ctx, record_id), &proof));
+    }
+}
+
+#[test]
+fn test_inclusion_rejects_tampering() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let other = create_test_user(&ctx, Role::Patient, "Other");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let ids = add_records(&ctx, &patient, &provider, 3);
+    let other_id = add_records(&ctx, &other, &provider, 1)[0];
+
+    let proof = ctx.client.get_merkle_proof(&ids[1]);
+    let mut leaf = leaf_for(&ctx, ids[1]);
+    leaf.data_hash = String::from_str(&ctx.env, "QmForged");
+    assert!(!ctx.client.verify_record_inclusion(&patient, &leaf, &proof));
+
+    let leaf = leaf_for(&ctx, ids[1]);
+    let mut moved = proof.clone();
+    moved.leaf_index = 0;
+    assert!(!ctx.client.verify_record_inclusion(&patient, &leaf, &moved));
+    let mut short = proof.clone();
+    short.siblings.pop_back();
+    assert!(!ctx.client.verify_record_inclusion(&patient, &leaf, &short));
+
+    // Another patient's record does not prove against this chart
+    let other_proof = ctx.client.get_merkle_proof(&other_id);
+    assert!(!ctx
+        .client
+        .verify_record_inclusion(&patient, &leaf_for(&ctx, other_id), &other_proof));
+    assert!(ctx
+        .client
+        .verify_record_inclusion(&other, &leaf_for(&ctx, other_id), &other_proof));
+}
+
+#[test]
+fn test_amendments_recommit_the_leaf() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let grantee = create_test_user(&ctx, Role::Staff, "Grantee");
+    let ids = add_records(&ctx, &patient, &provider, 2);
+    let old_leaf = leaf_for(&ctx, ids[0]);
+    let old_root = ctx.client.get_merkle_root(&patient);
+
+    // Re-encryption after a revocation amends the record's data hash
+    ctx.client
+        .grant_access(&patient, &patient, &grantee, &AccessLevel::Read, &3600);
+    ctx.client.revoke_access(&patient, &grantee);
+    ctx.client.rotate_record_key(
+        &provider,
+        &ids[0],
+        &String::from_str(&ctx.env, "QmReencrypted"),
+        &map![&ctx.env],
+    );
+
+    assert_ne!(ctx.client.get_merkle_root(&patient), old_root);
+    let proof = ctx.client.get_merkle_proof(&ids[0]);
+    assert!(!ctx
+        .client
+        .verify_record_inclusion(&patient, &old_leaf, &proof));
+    assert!(ctx
+        .client
+        .verify_record_inclusion(&patient, &leaf_for(&ctx, ids[0]), &proof));
+    assert!(ctx.client.verify_record_inclusion(
+        &patient,
+        &leaf_for(&ctx, ids[1]),
+        &ctx.client.get_merkle_proof(&ids[1])
+    ));
+}
+
+#[test]
+fn test_backfill_commits_records_from_before_the_tree() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let grantee = create_test_user(&ctx, Role::Staff, "Grantee");
+    let ids = add_records(&ctx, &patient, &provider, 3);
+    let root = ctx.client.get_merkle_root(&patient);
+
+    // Records stored before the upgrade have no leaves
+    ctx.env.as_contract(&ctx.client.address, || {
+        let storage = ctx.env.storage().persistent();
+        storage.remove(&(symbol_short!("MK_SIZE"), patient.clone()));
+        for record_id in &ids {
+            storage.remove(&(symbol_short!("MK_IDX"), *record_id));
+        }
+    });
+    let res = ctx.client.try_get_merkle_proof(&ids[0]);
+    assert_eq!(res.err(), Some(Ok(ContractError::RecordNotFound)));
+
+    // Rotation refuses them rather than leaving the amendment uncommitted
+    ctx.client
+        .grant_access(&patient, &patient, &grantee, &AccessLevel::Read, &3600);
+    ctx.client.revoke_access(&patient, &grantee);
+    let res = ctx.client.try_rotate_record_key(
+        &provider,
+        &ids[0],
+        &String::from_str(&ctx.env, "QmReencrypted"),
+        &map![&ctx.env],
+    );
+    assert_eq!(res, Err(Ok(ContractError::RecordNotFound)));
+
+    let res = ctx.client.try_backfill_merkle_tree(&provider, &patient);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(ctx.client.backfill_merkle_tree(&patient, &patient), 3);
+    assert_eq!(ctx.client.backfill_merkle_tree(&ctx.admin, &patient), 0);
+    assert_eq!(ctx.client.get_merkle_root(&patient), root);
+    ctx.client.rotate_record_key(
+        &provider,
+        &ids[0],
+        &String::from_str(&ctx.env, "QmReencrypted"),
+        &map![&ctx.env],
+    );
+}
@@ -0,0 +1,343 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env};
+use soroban_sdk::{vec, String};
+use vision_records::{
+    AccessLevel, ContractError, GrantTarget, OrgRole, Permission, PurposeOfUse, RecordType, Role,
+};
+
+#[test]
+fn test_org_grant_covers_on_duty_providers() {
+    let ctx = setup_test_env();
+    // Shared deployments drop the blanket `ReadAnyRecord`, so clinicians read
+    // through their clinic instead
+    ctx.client.set_role_permissions(
+        &ctx.admin,
+        &Role::Optometrist,
+        &vec![&ctx.env, Permission::WriteRecord, Permission::ManageAccess],
+    );
+    let org_admin = create_test_user(&ctx, Role::Staff, "Clinic Admin");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Clinic Provider");
+    let org_id = ctx.client.create_organization(
+        &ctx.admin,
+        &String::from_str(&ctx.env, "Downtown Eye Clinic"),
+        &org_admin,
+    );
+    ctx.client
+        .add_org_member(&org_admin, &org_id, &provider, &OrgRole::Provider);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let outside = create_test_user(&ctx, Role::Ophthalmologist, "Outside");
+    let record_id = create_test_record(
+        &ctx,
+        &outside,
+        &patient,
+        &outside,
+        RecordType::Examination,
+        "QmExam",
+    );
+
+    let target = GrantTarget::Organization(org_id);
+    ctx.client
+        .grant_group_access(&patient, &patient, &target, &AccessLevel::Read, &3600);
+    assert_eq!(
+        ctx.client.check_group_access(&patient, &target),
+        AccessLevel::Read
+    );
+
+    // Off duty, the grant does not apply
+    let res = ctx
+        .client
+        .try_access_record(&provider, &record_id, &PurposeOfUse::Treatment);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+
+    ctx.client.set_on_duty(&provider, &org_id, &provider, &true);
+    ctx.client
+        .access_record(&provider, &record_id, &PurposeOfUse::Treatment);
+
+    // Leaving the clinic ends access at once
+    ctx.client.remove_org_member(&org_admin, &org_id, &provider);
+    let res = ctx
+        .client
+        .try_access_record(&provider, &record_id, &PurposeOfUse::Treatment);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+
+    ctx.client.revoke_group_access(&patient, &target);
+    assert_eq!(
+        ctx.client.check_group_access(&patient, &target),
+        AccessLevel::None
+    );
+}
+
+#[test]
+fn test_check_access_resolves_current_members() {
+    let ctx = setup_test_env();
+    // Shared deployments drop the blanket `ReadAnyRecord`, so clinicians read
+    // through their clinic instead
+    ctx.client.set_role_permissions(
+        &ctx.admin,
+        &Role::Optometrist,
+        &vec![&ctx.env, Permission::WriteRecord, Permission::ManageAccess],
+    );
+    let org_admin = create_test_user(&ctx, Role::Staff, "Clinic Admin");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Clinic Provider");
+    let org_id = ctx.client.create_organization(
+        &ctx.admin,
+        &String::from_str(&ctx.env, "Downtown Eye Clinic"),
+        &org_admin,
+    );
+    ctx.client
+        .add_org_member(&org_admin, &org_id, &provider, &OrgRole::Provider);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let locum = create_test_user(&ctx, Role::Optometrist, "Locum");
+    let target = GrantTarget::Organization(org_id);
+
+    ctx.client
+        .grant_group_access(&patient, &patient, &target, &AccessLevel::Write, &3600);
+    ctx.client
+        .grant_access(&patient, &patient, &provider, &AccessLevel::Read, &3600);
+    assert_eq!(ctx.client.get_group_grants(&patient).len(), 1);
+
+    // The higher of the personal and group grants applies while on duty
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::Read
+    );
+    ctx.client.set_on_duty(&provider, &org_id, &provider, &true);
+    assert_eq!(
+        ctx.client.check_access(&patient, &provider),
+        AccessLevel::Write
+    );
+
+    // A provider joining later is covered without touching the grant
+    assert_eq!(ctx.client.check_access(&patient, &locum), AccessLevel::None);
+    ctx.client
+        .add_org_member(&org_admin, &org_id, &locum, &OrgRole::Provider);
+    ctx.client.set_on_duty(&locum, &org_id, &locum, &true);
+    assert_eq!(
+        ctx.client.check_access(&patient, &locum),
+        AccessLevel::Write
+    );
+}
+
+#[test]
+fn test_org_records_are_shared_within_the_clinic() {
+    let ctx = setup_test_env();
+    // Shared deployments drop the blanket `ReadAnyRecord`, so clinicians read
+    // through their clinic instead
+    ctx.client.set_role_permissions(
+        &ctx.admin,
+        &Role::Optometrist,
+        &vec![&ctx.env, Permission::WriteRecord, Permission::ManageAccess],
+    );
+    let org_admin = create_test_user(&ctx, Role::Staff, "Clinic Admin");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Clinic Provider");
+    let org_id = ctx.client.create_organization(
+        &ctx.admin,
+        &String::from_str(&ctx.env, "Downtown Eye Clinic"),
+        &org_admin,
+    );
+    ctx.client
+        .add_org_member(&org_admin, &org_id, &provider, &OrgRole::Provider);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let colleague = create_test_user(&ctx, Role::Optometrist, "Colleague");
+    let receptionist = create_test_user(&ctx, Role::Staff, "Receptionist");
+    ctx.client
+        .add_org_member(&org_admin, &org_id, &colleague, &OrgRole::Provider);
+    ctx.client
+        .add_org_member(&org_admin, &org_id, &receptionist, &OrgRole::Staff);
+
+    let record_id = ctx.client.add_org_record(
+        &provider,
+        &org_id,
+        &patient,
+        &provider,
+        &RecordType::Prescription,
+        &String::from_str(&ctx.env, "QmRx"),
+    );
+    assert_eq!(ctx.client.get_record_org(&record_id), Some(org_id));
+
+    let res = ctx
+        .client
+        .try_access_record(&colleague, &record_id, &PurposeOfUse::Treatment);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+
+    ctx.client
+        .set_on_duty(&org_admin, &org_id, &colleague, &true);
+    ctx.client
+        .access_record(&colleague, &record_id, &PurposeOfUse::Treatment);
+
+    // Staff members are not providers, on duty or not
+    ctx.client
+        .set_on_duty(&receptionist, &org_id, &receptionist, &true);
+    let res = ctx
+        .client
+        .try_access_record(&receptionist, &record_id, &PurposeOfUse::Treatment);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+}
+
+#[test]
+fn test_org_admins_manage_only_their_own_members() {
+    let ctx = setup_test_env();
+    let first_admin = create_test_user(&ctx, Role::Staff, "Downtown Admin");
+    let first_provider = create_test_user(&ctx, Role::Optometrist, "Downtown Provider");
+    let first_org = ctx.client.create_organization(
+        &ctx.admin,
+        &String::from_str(&ctx.env, "Downtown Eye Clinic"),
+        &first_admin,
+    );
+    ctx.client.add_org_member(
+        &first_admin,
+        &first_org,
+        &first_provider,
+        &OrgRole::Provider,
+    );
+    let second_admin = create_test_user(&ctx, Role::Staff, "Uptown Admin");
+    let second_provider = create_test_user(&ctx, Role::Optometrist, "Uptown Provider");
+    let second_org = ctx.client.create_organization(
+        &ctx.admin,
+        &String::from_str(&ctx.env, "Uptown Eye Clinic"),
+        &second_admin,
+    );
+    ctx.client.add_org_member(
+        &second_admin,
+        &second_org,
+        &second_provider,
+        &OrgRole::Provider,
+    );
+    let newcomer = create_test_user(&ctx, Role::Optometrist, "Newcomer");
+
+    let res =
+        ctx.client
+            .try_add_org_member(&first_admin, &second_org, &newcomer, &OrgRole::Provider);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx
+        .client
+        .try_remove_org_member(&first_admin, &second_org, &second_provider);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx
+        .client
+        .try_set_on_duty(&first_provider, &second_org, &second_provider, &true);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    // An organization always keeps at least one admin
+    let res = ctx
+        .client
+        .try_remove_org_member(&first_admin, &first_org, &first_admin);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res =
+        ctx.client
+            .try_add_org_member(&first_admin, &first_org, &first_admin, &OrgRole::Staff);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    ctx.client
+        .add_org_member(&first_admin, &first_org, &first_provider, &OrgRole::Admin);
+    ctx.client
+        .remove_org_member(&first_provider, &first_org, &first_admin);
+    assert_eq!(ctx.client.get_org_members(&first_org).len(), 1);
+}
+
+#[test]
+fn test_organization_validation() {
+    let ctx = setup_test_env();
+    // Shared deployments drop the blanket `ReadAnyRecord`, so clinicians read
+    // through their clinic instead
+    ctx.client.set_role_permissions(
+        &ctx.admin,
+        &Role::Optometrist,
+        &vec![&ctx.env, Permission::WriteRecord, Permission::ManageAccess],
+    );
+    let org_admin = create_test_user(&ctx, Role::Staff, "Clinic Admin");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Clinic Provider");
+    let org_id = ctx.client.create_organization(
+        &ctx.admin,
+        &String::from_str(&ctx.env, "Downtown Eye Clinic"),
+        &org_admin,
+    );
+    ctx.client
+        .add_org_member(&org_admin, &org_id, &provider, &OrgRole::Provider);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let outsider = create_test_user(&ctx, Role::Optometrist, "Outsider");
+
+    let res = ctx.client.try_create_organization(
+        &org_admin,
+        &String::from_str(&ctx.env, "Rogue Clinic"),
+        &org_admin,
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_get_organization(&99);
+    assert_eq!(res.err(), Some(Ok(ContractError::OrganizationNotFound)));
+    let res = ctx.client.try_grant_group_access(
+        &patient,
+        &patient,
+        &GrantTarget::Organization(99),
+        &AccessLevel::Read,
+        &3600,
+    );
+    assert_eq!(res, Err(Ok(ContractError::OrganizationNotFound)));
+
+    // Only the clinic's own providers can write records it owns
+    let res = ctx.client.try_add_org_record(
+        &outsider,
+        &org_id,
+        &patient,
+        &outsider,
+        &RecordType::Examination,
+        &String::from_str(&ctx.env, "QmExam"),
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_org_providers_need_clinical_credentials() {
+    let ctx = setup_test_env();
+    // Shared deployments drop the blanket `ReadAnyRecord`, so clinicians read
+    // through their clinic instead
+    ctx.client.set_role_permissions(
+        &ctx.admin,
+        &Role::Optometrist,
+        &vec![&ctx.env, Permission::WriteRecord, Permission::ManageAccess],
+    );
+    let org_admin = create_test_user(&ctx, Role::Staff, "Clinic Admin");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Clinic Provider");
+    let org_id = ctx.client.create_organization(
+        &ctx.admin,
+        &String::from_str(&ctx.env, "Downtown Eye Clinic"),
+        &org_admin,
+    );
+    ctx.client
+        .add_org_member(&org_admin, &org_id, &provider, &OrgRole::Provider);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let record_id = ctx.client.add_org_record(
+        &provider,
+        &org_id,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&ctx.env, "QmExam"),
+    );
+
+    // A patient or staff member cannot be made an org provider
+    let other_patient = create_test_user(&ctx, Role::Patient, "Other Patient");
+    let res =
+        ctx.client
+            .try_add_org_member(&org_admin, &org_id, &other_patient, &OrgRole::Provider);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    let res = ctx
+        .client
+        .try_add_org_member(&org_admin, &org_id, &org_admin, &OrgRole::Provider);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // A provider whose credential is suspended stops reading as one
+    let colleague = create_test_user(&ctx, Role::Optometrist, "Colleague");
+    ctx.client
+        .add_org_member(&org_admin, &org_id, &colleague, &OrgRole::Provider);
+    ctx.client
+        .set_on_duty(&colleague, &org_id, &colleague, &true);
+    ctx.client
+        .access_record(&colleague, &record_id, &PurposeOfUse::Treatment);
+    ctx.client.suspend_credential(&ctx.admin, &colleague);
+    let res = ctx
+        .client
+        .try_access_record(&colleague, &record_id, &PurposeOfUse::Treatment);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env, TestContext};
+use soroban_sdk::testutils::Ledger;
+use soroban_sdk::String;
+use vision_records::{ContractError, EyeRx, LensType, PrismBase, RecordType, Role};
+
+fn spectacle_eye(ctx: &TestContext, sphere: i32, cylinder: i32, axis: u32) -> EyeRx {
+    EyeRx {
+        sphere,
+        cylinder,
+        axis,
+        add: 0,
+        prism: 0,
+        prism_base: PrismBase::None,
+        pd: 3150,
+        base_curve: 0,
+        diameter: 0,
+        brand: String::from_str(&ctx.env, ""),
+    }
+}
+
+fn contact_eye(ctx: &TestContext, sphere: i32) -> EyeRx {
+    EyeRx {
+        base_curve: 860,
+        diameter: 1420,
+        brand: String::from_str(&ctx.env, "Acuvue Oasys"),
+        ..spectacle_eye(ctx, sphere, 0, 0)
+    }
+}
+
+#[test]
+fn test_add_and_verify_spectacle_prescription() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Prescription,
+        "QmRx",
+    );
+
+    let expires_at = 1_000 + 365 * 86400;
+    let rx_id = ctx.client.add_prescription(
+        &provider,
+        &record_id,
+        &LensType::Spectacle,
+        &spectacle_eye(&ctx, -225, -50, 90),
+        &spectacle_eye(&ctx, -200, -75, 85),
+        &expires_at,
+    );
+    assert_eq!(rx_id, 1);
+
+    let rx = ctx.client.get_prescription(&rx_id);
+    assert_eq!(rx.record_id, record_id);
+    assert_eq!(rx.patient, patient);
+    assert_eq!(rx.left_eye.sphere, -225);
+    assert_eq!(rx.right_eye.axis, 85);
+    assert_eq!(ctx.client.get_patient_prescriptions(&patient).len(), 1);
+
+    assert!(ctx.client.verify_prescription(&rx_id, &patient));
+
+    // Wrong patient or unknown Rx never verifies
+    let other = create_test_user(&ctx, Role::Patient, "Other");
+    assert!(!ctx.client.verify_prescription(&rx_id, &other));
+    assert!(!ctx.client.verify_prescription(&99, &patient));
+
+    // Expired exactly at `expires_at`
+    ctx.env.ledger().set_timestamp(expires_at - 1);
+    assert!(ctx.client.verify_prescription(&rx_id, &patient));
+    ctx.env.ledger().set_timestamp(expires_at);
+    assert!(!ctx.client.verify_prescription(&rx_id, &patient));
+}
+
+#[test]
+fn test_contact_lens_prescription_requires_fit() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Prescription,
+        "QmCL",
+    );
+
+    let res = ctx.client.try_add_prescription(
+        &provider,
+        &record_id,
+        &LensType::ContactLens,
+        &spectacle_eye(&ctx, -300, 0, 0),
+        &contact_eye(&ctx, -300),
+        &86400,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let rx_id = ctx.client.add_prescription(
+        &provider,
+        &record_id,
+        &LensType::ContactLens,
+        &contact_eye(&ctx, -300),
+        &contact_eye(&ctx, -275),
+        &86400,
+    );
+    let rx = ctx.client.get_prescription(&rx_id);
+    assert_eq!(rx.lens_type, LensType::ContactLens);
+    assert_eq!(rx.right_eye.base_curve, 860);
+    assert_eq!(
+        rx.left_eye.brand,
+        String::from_str(&ctx.env, "Acuvue Oasys")
+    );
+}
+
+#[test]
+fn test_add_prescription_validation() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(5_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let other_provider = create_test_user(&ctx, Role::Optometrist, "Other");
+    let exam_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Prescription,
+        "QmRx",
+    );
+    let eye = spectacle_eye(&ctx, -100, 0, 0);
+
+    // Must be attached to a Prescription record
+    let res = ctx.client.try_add_prescription(
+        &provider,
+        &exam_id,
+        &LensType::Spectacle,
+        &eye,
+        &eye,
+        &10_000,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // Axis above 180 degrees
+    let res = ctx.client.try_add_prescription(
+        &provider,
+        &record_id,
+        &LensType::Spectacle,
+        &spectacle_eye(&ctx, -100, -50, 181),
+        &eye,
+        &10_000,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // Spectacle Rx carries no contact lens fit
+    let res = ctx.client.try_add_prescription(
+        &provider,
+        &record_id,
+        &LensType::Spectacle,
+        &contact_eye(&ctx, -100),
+        &eye,
+        &10_000,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // Already expired
+    let res = ctx.client.try_add_prescription(
+        &provider,
+        &record_id,
+        &LensType::Spectacle,
+        &eye,
+        &eye,
+        &5_000,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // Only the record's provider may attach the Rx
+    let res = ctx.client.try_add_prescription(
+        &other_provider,
+        &record_id,
+        &LensType::Spectacle,
+        &eye,
+        &eye,
+        &10_000,
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    let res = ctx.client.try_get_prescription(&7);
+    assert_eq!(res.err(), Some(Ok(ContractError::PrescriptionNotFound)));
+}
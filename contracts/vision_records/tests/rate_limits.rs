@@ -0,0 +1,199 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env, TestContext};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{Address, String};
+use vision_records::{
+    AccessLevel, ContractError, GrantTarget, RateLimit, RateLimitScope, RateLimitedAction,
+    RecordType, Role,
+};
+
+fn add_record(ctx: &TestContext, provider: &Address, patient: &Address) -> u64 {
+    create_test_record(
+        ctx,
+        provider,
+        patient,
+        provider,
+        RecordType::Examination,
+        "QmExam",
+    )
+}
+
+#[test]
+fn test_caller_limit_on_records() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let other_provider = create_test_user(&ctx, Role::Optometrist, "Other Provider");
+
+    // Disabled until configured
+    assert_eq!(
+        ctx.client
+            .get_rate_limit(&RateLimitedAction::AddRecord, &RateLimitScope::Caller),
+        None
+    );
+    for _ in 0..5 {
+        add_record(&ctx, &provider, &patient);
+    }
+
+    ctx.client.set_rate_limit(
+        &ctx.admin,
+        &RateLimitedAction::AddRecord,
+        &RateLimitScope::Caller,
+        &2,
+        &100,
+    );
+    assert_eq!(
+        ctx.client
+            .get_rate_limit(&RateLimitedAction::AddRecord, &RateLimitScope::Caller),
+        Some(RateLimit {
+            max_calls: 2,
+            window_seconds: 100
+        })
+    );
+    add_record(&ctx, &provider, &patient);
+    add_record(&ctx, &provider, &patient);
+    let hash = String::from_str(&ctx.env, "QmSpam");
+    let res = ctx.client.try_add_record(
+        &provider,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &hash,
+    );
+    assert_eq!(res, Err(Ok(ContractError::RateLimited)));
+    assert_eq!(ctx.client.get_record_count(), 7);
+
+    // Other callers have their own budget, and windows reset
+    add_record(&ctx, &other_provider, &patient);
+    ctx.env.ledger().set_timestamp(1_100);
+    add_record(&ctx, &provider, &patient);
+}
+
+#[test]
+fn test_patient_limits() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let first = create_test_user(&ctx, Role::Optometrist, "First");
+    let second = create_test_user(&ctx, Role::Optometrist, "Second");
+    ctx.client.set_rate_limit(
+        &ctx.admin,
+        &RateLimitedAction::AddRecord,
+        &RateLimitScope::Patient,
+        &1,
+        &3600,
+    );
+    ctx.client.set_rate_limit(
+        &ctx.admin,
+        &RateLimitedAction::GrantAccess,
+        &RateLimitScope::Patient,
+        &1,
+        &3600,
+    );
+
+    // Spreading writes over several providers doesn't get around the limit
+    add_record(&ctx, &first, &patient);
+    let res = ctx.client.try_add_record(
+        &second,
+        &patient,
+        &second,
+        &RecordType::Examination,
+        &String::from_str(&ctx.env, "QmExam"),
+    );
+    assert_eq!(res, Err(Ok(ContractError::RateLimited)));
+
+    ctx.client
+        .grant_access(&patient, &patient, &first, &AccessLevel::Read, &3600);
+    let res = ctx
+        .client
+        .try_grant_access(&patient, &patient, &second, &AccessLevel::Read, &3600);
+    assert_eq!(res, Err(Ok(ContractError::RateLimited)));
+
+    // Group grants draw on the same budget
+    let team_id = ctx
+        .client
+        .create_care_team(&patient, &String::from_str(&ctx.env, "Clinic"));
+    let res = ctx.client.try_grant_group_access(
+        &patient,
+        &patient,
+        &GrantTarget::CareTeam(team_id),
+        &AccessLevel::Read,
+        &3600,
+    );
+    assert_eq!(res, Err(Ok(ContractError::RateLimited)));
+}
+
+#[test]
+fn test_registration_limit_and_configuration() {
+    let ctx = setup_test_env();
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+
+    let res = ctx.client.try_set_rate_limit(
+        &staff,
+        &RateLimitedAction::RegisterUser,
+        &RateLimitScope::Caller,
+        &1,
+        &60,
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_set_rate_limit(
+        &ctx.admin,
+        &RateLimitedAction::RegisterUser,
+        &RateLimitScope::Caller,
+        &1,
+        &0,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    ctx.client.set_rate_limit(
+        &ctx.admin,
+        &RateLimitedAction::RegisterUser,
+        &RateLimitScope::Caller,
+        &1,
+        &60,
+    );
+    let name = String::from_str(&ctx.env, "User");
+    ctx.client
+        .register_user(&staff, &Address::generate(&ctx.env), &Role::Patient, &name);
+    let res =
+        ctx.client
+            .try_register_user(&staff, &Address::generate(&ctx.env), &Role::Patient, &name);
+    assert_eq!(res, Err(Ok(ContractError::RateLimited)));
+
+    // Removing the limit lifts it at once
+    ctx.client.set_rate_limit(
+        &ctx.admin,
+        &RateLimitedAction::RegisterUser,
+        &RateLimitScope::Caller,
+        &0,
+        &0,
+    );
+    ctx.client
+        .register_user(&staff, &Address::generate(&ctx.env), &Role::Patient, &name);
+}
+
+#[test]
+fn test_huge_window_does_not_overflow() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+
+    ctx.client.set_rate_limit(
+        &ctx.admin,
+        &RateLimitedAction::AddRecord,
+        &RateLimitScope::Caller,
+        &1,
+        &u64::MAX,
+    );
+    add_record(&ctx, &provider, &patient);
+    let res = ctx.client.try_add_record(
+        &provider,
+        &patient,
+        &provider,
+        &RecordType::Examination,
+        &String::from_str(&ctx.env, "QmExam"),
+    );
+    assert_eq!(res, Err(Ok(ContractError::RateLimited)));
+}
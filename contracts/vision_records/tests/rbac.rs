@@ -1,9 +1,16 @@
 mod common;
 
-use common::{create_test_user, setup_test_env};
-use soroban_sdk::testutils::{Address as _, Ledger};
-use soroban_sdk::Address;
-use vision_records::{AccessLevel, Permission, Role};
+use common::{attest_test_credential, create_test_user, setup_test_env};
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{symbol_short, Address, IntoVal, String, Symbol, Val, Vec};
+use vision_records::{rbac, AccessLevel, ContractError, Permission, RecordType, Role};
+
+/// The leading topic symbol of the last event published
+fn last_topic(ctx: &common::TestContext) -> Symbol {
+    let events = ctx.env.events().all();
+    let (_, topics, _): (_, Vec<Val>, Val) = events.last().unwrap();
+    topics.get(0).unwrap().into_val(&ctx.env)
+}
 
 #[test]
 fn test_role_hierarchy_and_inheritance() {
@@ -59,6 +66,11 @@ fn test_custom_permission_grants() {
 
     ctx.client
         .grant_custom_permission(&ctx.admin, &staff, &Permission::WriteRecord);
+    // A clinical grant only applies once the holder is credentialed
+    assert!(!ctx
+        .client
+        .check_permission(&staff, &Permission::WriteRecord));
+    attest_test_credential(&ctx, &staff, Role::Optometrist);
     assert!(ctx
         .client
         .check_permission(&staff, &Permission::WriteRecord));
@@ -204,3 +216,251 @@ fn test_access_control_with_generated_addresses() {
         AccessLevel::None
     );
 }
+
+#[test]
+fn test_rbac_mutations_emit_events() {
+    let ctx = setup_test_env();
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+    assert_eq!(ctx.env.events().all().len(), 2);
+
+    ctx.client
+        .grant_custom_permission(&ctx.admin, &staff, &Permission::WriteRecord);
+    assert_eq!(ctx.env.events().all().len(), 1);
+    assert_eq!(last_topic(&ctx), symbol_short!("PERM_GRT"));
+
+    ctx.client
+        .revoke_custom_permission(&ctx.admin, &staff, &Permission::ManageUsers);
+    assert_eq!(ctx.env.events().all().len(), 1);
+    assert_eq!(last_topic(&ctx), symbol_short!("PERM_REV"));
+
+    let delegatee = create_test_user(&ctx, Role::Patient, "Delegatee");
+    ctx.client
+        .delegate_role(&staff, &delegatee, &Role::Staff, &0);
+    assert_eq!(ctx.env.events().all().len(), 1);
+    assert_eq!(last_topic(&ctx), symbol_short!("DELEGATE"));
+}
+
+#[test]
+fn test_expire_role_removes_assignment() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let staff = Address::generate(&ctx.env);
+    ctx.env.as_contract(&ctx.client.address, || {
+        rbac::assign_role(
+            &ctx.env,
+            ctx.admin.clone(),
+            staff.clone(),
+            Role::Staff,
+            2_000,
+        );
+    });
+
+    // Still active
+    let res = ctx.client.try_expire_role(&ctx.admin, &staff);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+    assert!(ctx
+        .client
+        .check_permission(&staff, &Permission::ManageUsers));
+
+    ctx.env.ledger().set_timestamp(2_000);
+    ctx.client.expire_role(&ctx.admin, &staff);
+    assert_eq!(last_topic(&ctx), symbol_short!("ROLE_EXP"));
+    assert!(!ctx
+        .client
+        .check_permission(&staff, &Permission::ManageUsers));
+
+    // Nothing left to expire
+    let res = ctx.client.try_expire_role(&ctx.admin, &staff);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_role_permission_matrix_is_editable() {
+    let ctx = setup_test_env();
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+    assert_eq!(
+        ctx.client.get_role_permissions(&Role::Staff),
+        Vec::from_array(
+            &ctx.env,
+            [Permission::ManageUsers, Permission::ManageAppointments]
+        )
+    );
+
+    // Staff may now manage access, and lose user management
+    ctx.client.set_role_permissions(
+        &ctx.admin,
+        &Role::Staff,
+        &Vec::from_array(
+            &ctx.env,
+            [Permission::ManageAccess, Permission::ManageAccess],
+        ),
+    );
+    assert_eq!(last_topic(&ctx), symbol_short!("ROLE_PRM"));
+    assert_eq!(ctx.client.get_role_permissions(&Role::Staff).len(), 1);
+    assert!(ctx
+        .client
+        .check_permission(&staff, &Permission::ManageAccess));
+    assert!(!ctx
+        .client
+        .check_permission(&staff, &Permission::ManageUsers));
+
+    // Only system admins edit the matrix, and admins cannot lock themselves out
+    let res = ctx.client.try_set_role_permissions(
+        &staff,
+        &Role::Staff,
+        &Vec::from_array(&ctx.env, [Permission::SystemAdmin]),
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_set_role_permissions(
+        &ctx.admin,
+        &Role::Admin,
+        &Vec::from_array(&ctx.env, [Permission::ManageUsers]),
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_stored_matrix_keeps_credential_gating() {
+    let ctx = setup_test_env();
+    let provider = Address::generate(&ctx.env);
+    ctx.client.register_user(
+        &ctx.admin,
+        &provider,
+        &Role::Optometrist,
+        &soroban_sdk::String::from_str(&ctx.env, "Dr. Unverified"),
+    );
+
+    ctx.client.set_role_permissions(
+        &ctx.admin,
+        &Role::Optometrist,
+        &Vec::from_array(
+            &ctx.env,
+            [Permission::WriteRecord, Permission::ReadAuditLog],
+        ),
+    );
+    assert!(ctx
+        .client
+        .check_permission(&provider, &Permission::ReadAuditLog));
+    assert!(!ctx
+        .client
+        .check_permission(&provider, &Permission::WriteRecord));
+}
+
+#[test]
+fn test_concurrent_roles_union_permissions() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let doctor = create_test_user(&ctx, Role::Ophthalmologist, "Dr. Admin");
+
+    ctx.client
+        .add_role(&ctx.admin, &doctor, &Role::Admin, &5_000);
+    assert_eq!(last_topic(&ctx), symbol_short!("ROLE_ADD"));
+    assert_eq!(
+        ctx.client.get_user_roles(&doctor),
+        Vec::from_array(&ctx.env, [Role::Ophthalmologist, Role::Admin])
+    );
+    assert!(ctx
+        .client
+        .check_permission(&doctor, &Permission::SystemAdmin));
+    assert!(ctx
+        .client
+        .check_permission(&doctor, &Permission::WriteRecord));
+
+    // Custom revokes subtract from the union
+    ctx.client
+        .revoke_custom_permission(&ctx.admin, &doctor, &Permission::VerifyCredentials);
+    assert!(!ctx
+        .client
+        .check_permission(&doctor, &Permission::VerifyCredentials));
+
+    // Each role expires on its own
+    ctx.env.ledger().set_timestamp(5_000);
+    assert!(!ctx
+        .client
+        .check_permission(&doctor, &Permission::SystemAdmin));
+    assert!(ctx
+        .client
+        .check_permission(&doctor, &Permission::WriteRecord));
+
+    ctx.client.add_role(&ctx.admin, &doctor, &Role::Auditor, &0);
+    ctx.client.remove_role(&ctx.admin, &doctor, &Role::Auditor);
+    assert_eq!(last_topic(&ctx), symbol_short!("ROLE_REM"));
+    assert!(!ctx
+        .client
+        .check_permission(&doctor, &Permission::ReadAuditLog));
+}
+
+#[test]
+fn test_add_role_authorization() {
+    let ctx = setup_test_env();
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+
+    // Staff manage users but cannot hand out Admin, nor any role that can
+    // itself manage users
+    let res = ctx.client.try_add_role(&staff, &staff, &Role::Admin, &0);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_add_role(&staff, &patient, &Role::Staff, &0);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    ctx.client.add_role(&staff, &patient, &Role::Researcher, &0);
+    assert!(ctx
+        .client
+        .check_permission(&patient, &Permission::ReadResearchData));
+
+    // The check follows the role's stored permissions, not its name
+    ctx.client.set_role_permissions(
+        &ctx.admin,
+        &Role::Auditor,
+        &Vec::from_array(
+            &ctx.env,
+            [Permission::ReadAuditLog, Permission::VerifyCredentials],
+        ),
+    );
+    let res = ctx
+        .client
+        .try_add_role(&staff, &patient, &Role::Auditor, &0);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    let res = ctx
+        .client
+        .try_add_role(&patient, &patient, &Role::Staff, &0);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    // The registered role is not an additional role
+    let res = ctx
+        .client
+        .try_remove_role(&ctx.admin, &patient, &Role::Patient);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_privileged_custom_permissions_need_system_admin() {
+    let ctx = setup_test_env();
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+    let user = create_test_user(&ctx, Role::Patient, "User");
+
+    for permission in [
+        Permission::SystemAdmin,
+        Permission::ManageUsers,
+        Permission::VerifyCredentials,
+    ] {
+        let res = ctx
+            .client
+            .try_grant_custom_permission(&staff, &staff, &permission);
+        assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    }
+    assert!(!ctx
+        .client
+        .check_permission(&staff, &Permission::SystemAdmin));
+
+    // Staff may still grant WriteRecord, but it is inert without a credential
+    ctx.client
+        .grant_custom_permission(&staff, &user, &Permission::WriteRecord);
+    let res = ctx.client.try_add_record(
+        &user,
+        &user,
+        &user,
+        &RecordType::Examination,
+        &String::from_str(&ctx.env, "QmExam"),
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+}
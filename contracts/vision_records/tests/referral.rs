@@ -0,0 +1,369 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env, TestContext};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{vec, Address, String, Vec};
+use vision_records::{
+    AccessLevel, ContractError, RecordType, ReferralStatus, ReferralUrgency, Role,
+};
+
+/// Refer `patient` from `optometrist` to `specialist` for one record
+fn refer(
+    ctx: &TestContext,
+    patient: &Address,
+    optometrist: &Address,
+    specialist: &Address,
+    record_id: u64,
+) -> u64 {
+    ctx.client.create_referral(
+        optometrist,
+        patient,
+        specialist,
+        &String::from_str(&ctx.env, "Suspected glaucoma"),
+        &ReferralUrgency::Urgent,
+        &vec![&ctx.env, record_id],
+    )
+}
+
+#[test]
+fn test_referral_grants_scoped_read_access() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let optometrist = create_test_user(&ctx, Role::Optometrist, "Opto");
+    let specialist = create_test_user(&ctx, Role::Ophthalmologist, "Ophtho");
+    let exam_id = create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let other_id = create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::LabResult,
+        "QmLab",
+    );
+
+    assert_eq!(
+        ctx.client.check_record_access(&exam_id, &specialist),
+        AccessLevel::None
+    );
+
+    let referral_id = refer(&ctx, &patient, &optometrist, &specialist, exam_id);
+    let referral = ctx.client.get_referral(&referral_id);
+    assert_eq!(referral.status, ReferralStatus::Pending);
+    assert_eq!(referral.urgency, ReferralUrgency::Urgent);
+    assert_eq!(referral.outcome_record_id, None);
+
+    // Read access covers only the referenced record, not the whole chart
+    assert_eq!(
+        ctx.client.check_record_access(&exam_id, &specialist),
+        AccessLevel::Read
+    );
+    assert_eq!(
+        ctx.client.check_record_access(&other_id, &specialist),
+        AccessLevel::None
+    );
+    assert_eq!(
+        ctx.client.check_access(&patient, &specialist),
+        AccessLevel::None
+    );
+
+    // ...and expires
+    ctx.env.ledger().set_timestamp(referral.access_expires_at);
+    assert_eq!(
+        ctx.client.check_record_access(&exam_id, &specialist),
+        AccessLevel::None
+    );
+
+    assert_eq!(ctx.client.get_patient_referrals(&patient).len(), 1);
+    assert_eq!(ctx.client.get_provider_referrals(&optometrist).len(), 1);
+    assert_eq!(ctx.client.get_provider_referrals(&specialist).len(), 1);
+}
+
+#[test]
+fn test_create_referral_requires_roles() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let optometrist = create_test_user(&ctx, Role::Optometrist, "Opto");
+    let specialist = create_test_user(&ctx, Role::Ophthalmologist, "Ophtho");
+    let exam_id = create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let other_optometrist = create_test_user(&ctx, Role::Optometrist, "Other Opto");
+    let reason = String::from_str(&ctx.env, "Cataract evaluation");
+    let records = vec![&ctx.env, exam_id];
+
+    // Only optometrists refer
+    let res = ctx.client.try_create_referral(
+        &specialist,
+        &patient,
+        &optometrist,
+        &reason,
+        &ReferralUrgency::Routine,
+        &records,
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    // ...and only to ophthalmologists
+    let res = ctx.client.try_create_referral(
+        &optometrist,
+        &patient,
+        &other_optometrist,
+        &reason,
+        &ReferralUrgency::Routine,
+        &records,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // At least one record must be referenced
+    let res = ctx.client.try_create_referral(
+        &optometrist,
+        &patient,
+        &specialist,
+        &reason,
+        &ReferralUrgency::Routine,
+        &Vec::new(&ctx.env),
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // An optometrist cannot share records they have no access to
+    let res = ctx.client.try_create_referral(
+        &other_optometrist,
+        &patient,
+        &specialist,
+        &reason,
+        &ReferralUrgency::Routine,
+        &records,
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_create_referral_requires_credentials() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let optometrist = create_test_user(&ctx, Role::Optometrist, "Opto");
+    let specialist = create_test_user(&ctx, Role::Ophthalmologist, "Ophtho");
+    let exam_id = create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let reason = String::from_str(&ctx.env, "Cataract evaluation");
+    let records = vec![&ctx.env, exam_id];
+
+    // A registered ophthalmologist without a credential gets no access
+    let uncredentialed = Address::generate(&ctx.env);
+    ctx.client.register_user(
+        &ctx.admin,
+        &uncredentialed,
+        &Role::Ophthalmologist,
+        &String::from_str(&ctx.env, "Unverified"),
+    );
+    let res = ctx.client.try_create_referral(
+        &optometrist,
+        &patient,
+        &uncredentialed,
+        &reason,
+        &ReferralUrgency::Routine,
+        &records,
+    );
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // A suspended optometrist can no longer refer
+    ctx.client.suspend_credential(&ctx.admin, &optometrist);
+    let res = ctx.client.try_create_referral(
+        &optometrist,
+        &patient,
+        &specialist,
+        &reason,
+        &ReferralUrgency::Routine,
+        &records,
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_referral_accept_and_complete() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let optometrist = create_test_user(&ctx, Role::Optometrist, "Opto");
+    let specialist = create_test_user(&ctx, Role::Ophthalmologist, "Ophtho");
+    let exam_id = create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let referral_id = refer(&ctx, &patient, &optometrist, &specialist, exam_id);
+
+    // Cannot complete before accepting
+    let diagnosis = create_test_record(
+        &ctx,
+        &specialist,
+        &patient,
+        &specialist,
+        RecordType::Diagnosis,
+        "QmDiag",
+    );
+    let res = ctx
+        .client
+        .try_complete_referral(&specialist, &referral_id, &diagnosis);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    // Only the receiving specialist may accept
+    let res = ctx.client.try_accept_referral(&optometrist, &referral_id);
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+
+    ctx.client.accept_referral(&specialist, &referral_id);
+    assert_eq!(
+        ctx.client.get_referral(&referral_id).status,
+        ReferralStatus::Accepted
+    );
+
+    // The outcome must be a Diagnosis or Surgery record
+    let res = ctx
+        .client
+        .try_complete_referral(&specialist, &referral_id, &exam_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    ctx.client
+        .complete_referral(&specialist, &referral_id, &diagnosis);
+    let referral = ctx.client.get_referral(&referral_id);
+    assert_eq!(referral.status, ReferralStatus::Completed);
+    assert_eq!(referral.outcome_record_id, Some(diagnosis));
+}
+
+#[test]
+fn test_decline_referral_withdraws_access() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let optometrist = create_test_user(&ctx, Role::Optometrist, "Opto");
+    let specialist = create_test_user(&ctx, Role::Ophthalmologist, "Ophtho");
+    let exam_id = create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let referral_id = refer(&ctx, &patient, &optometrist, &specialist, exam_id);
+
+    ctx.client.decline_referral(&specialist, &referral_id);
+    assert_eq!(
+        ctx.client.get_referral(&referral_id).status,
+        ReferralStatus::Declined
+    );
+    assert_eq!(
+        ctx.client.check_record_access(&exam_id, &specialist),
+        AccessLevel::None
+    );
+
+    // A declined referral cannot be accepted afterwards
+    let res = ctx.client.try_accept_referral(&specialist, &referral_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+
+    let res = ctx.client.try_get_referral(&99);
+    assert_eq!(res.err(), Some(Ok(ContractError::ReferralNotFound)));
+}
+
+#[test]
+fn test_overlapping_referrals_keep_shared_access() {
+    let ctx = setup_test_env();
+    ctx.env.ledger().set_timestamp(1_000);
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let optometrist = create_test_user(&ctx, Role::Optometrist, "Opto");
+    let specialist = create_test_user(&ctx, Role::Ophthalmologist, "Ophtho");
+    let exam_id = create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let first_id = refer(&ctx, &patient, &optometrist, &specialist, exam_id);
+
+    ctx.env.ledger().set_timestamp(5_000);
+    let second_id = refer(&ctx, &patient, &optometrist, &specialist, exam_id);
+    let second = ctx.client.get_referral(&second_id);
+
+    // Declining the later referral falls back to the earlier one's expiry
+    ctx.client.decline_referral(&specialist, &second_id);
+    assert_eq!(
+        ctx.client.check_record_access(&exam_id, &specialist),
+        AccessLevel::Read
+    );
+    let first = ctx.client.get_referral(&first_id);
+    ctx.env.ledger().set_timestamp(first.access_expires_at);
+    assert_eq!(
+        ctx.client.check_record_access(&exam_id, &specialist),
+        AccessLevel::None
+    );
+    assert!(first.access_expires_at < second.access_expires_at);
+}
+
+#[test]
+fn test_decline_keeps_access_of_active_referral() {
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let optometrist = create_test_user(&ctx, Role::Optometrist, "Opto");
+    let specialist = create_test_user(&ctx, Role::Ophthalmologist, "Ophtho");
+    let exam_id = create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::Examination,
+        "QmExam",
+    );
+    let first_id = refer(&ctx, &patient, &optometrist, &specialist, exam_id);
+    let second_id = refer(&ctx, &patient, &optometrist, &specialist, exam_id);
+
+    ctx.client.accept_referral(&specialist, &first_id);
+    ctx.client.decline_referral(&specialist, &second_id);
+    assert_eq!(
+        ctx.client.check_record_access(&exam_id, &specialist),
+        AccessLevel::Read
+    );
+}
+
+#[test]
+fn test_referral_transitions_emit_events() {
+    use soroban_sdk::testutils::Events;
+    let ctx = setup_test_env();
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let optometrist = create_test_user(&ctx, Role::Optometrist, "Opto");
+    let specialist = create_test_user(&ctx, Role::Ophthalmologist, "Ophtho");
+    let exam_id = create_test_record(
+        &ctx,
+        &optometrist,
+        &patient,
+        &optometrist,
+        RecordType::Examination,
+        "QmExam",
+    );
+
+    let referral_id = refer(&ctx, &patient, &optometrist, &specialist, exam_id);
+    assert_eq!(ctx.env.events().all().len(), 1);
+
+    ctx.client.accept_referral(&specialist, &referral_id);
+    assert_eq!(ctx.env.events().all().len(), 1);
+}
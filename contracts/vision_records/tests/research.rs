@@ -0,0 +1,208 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env, TestContext};
+use soroban_sdk::{vec, Address, String, TryFromVal, Vec};
+use vision_records::{
+    events::{ResearchOptInEvent, ResearchOptOutEvent},
+    ContractError, PurposeOfUse, RecordType, Role,
+};
+
+/// Lift the default directive's denial of research without allowing it
+/// for grants
+fn allow_research(ctx: &TestContext, patient: &Address) {
+    ctx.client.set_consent_directive(
+        patient,
+        &vec![&ctx.env, PurposeOfUse::Treatment],
+        &Vec::new(&ctx.env),
+    );
+}
+
+#[test]
+fn test_cohort_lists_opted_in_record_hashes() {
+    let ctx = setup_test_env();
+    let researcher = create_test_user(&ctx, Role::Researcher, "Researcher");
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Ophthalmologist, "Provider");
+    for (record_type, hash) in [
+        (RecordType::Examination, "QmExam"),
+        (RecordType::Diagnosis, "QmDiag"),
+    ] {
+        create_test_record(&ctx, &provider, &patient, &provider, record_type, hash);
+    }
+    let study_id = ctx
+        .client
+        .create_study(&researcher, &String::from_str(&ctx.env, "Glaucoma cohort"));
+    allow_research(&ctx, &patient);
+    assert_eq!(ctx.client.get_study(&study_id).investigator, researcher);
+    assert_eq!(ctx.client.get_study_cohort(&researcher, &study_id).len(), 0);
+
+    // Only examinations are shared with this study
+    ctx.client.opt_in_research(
+        &patient,
+        &study_id,
+        &vec![&ctx.env, RecordType::Examination],
+    );
+    let cohort = ctx.client.get_study_cohort(&researcher, &study_id);
+    assert_eq!(cohort.len(), 1);
+    let member = cohort.get(0).unwrap();
+    assert_eq!(member.records.len(), 1);
+    assert_eq!(
+        member.records.get(0).unwrap().record_type,
+        RecordType::Examination
+    );
+
+    // Withdrawal takes effect immediately
+    ctx.client.withdraw_research_consent(&patient, &study_id);
+    assert_eq!(ctx.client.get_study_cohort(&researcher, &study_id).len(), 0);
+    let res = ctx
+        .client
+        .try_withdraw_research_consent(&patient, &study_id);
+    assert_eq!(res, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_pseudonyms_differ_between_studies() {
+    let ctx = setup_test_env();
+    let researcher = create_test_user(&ctx, Role::Researcher, "Researcher");
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Ophthalmologist, "Provider");
+    for (record_type, hash) in [
+        (RecordType::Examination, "QmExam"),
+        (RecordType::Diagnosis, "QmDiag"),
+    ] {
+        create_test_record(&ctx, &provider, &patient, &provider, record_type, hash);
+    }
+    let study_id = ctx
+        .client
+        .create_study(&researcher, &String::from_str(&ctx.env, "Glaucoma cohort"));
+    allow_research(&ctx, &patient);
+    let other_study = ctx
+        .client
+        .create_study(&researcher, &String::from_str(&ctx.env, "Myopia cohort"));
+
+    // A blanket opt-in covers every study and every record type
+    ctx.client
+        .opt_in_research(&patient, &0, &Vec::new(&ctx.env));
+    let first = ctx.client.get_study_cohort(&researcher, &study_id);
+    let second = ctx.client.get_study_cohort(&researcher, &other_study);
+    assert_eq!(first.get(0).unwrap().records.len(), 2);
+    assert_ne!(
+        first.get(0).unwrap().pseudonym,
+        second.get(0).unwrap().pseudonym
+    );
+    // So are the digests standing in for the same record's data hash
+    assert_ne!(
+        first.get(0).unwrap().records.get(0).unwrap().data_digest,
+        second.get(0).unwrap().records.get(0).unwrap().data_digest
+    );
+
+    // Pseudonyms are stable within a study
+    let again = ctx.client.get_study_cohort(&researcher, &study_id);
+    assert_eq!(
+        first.get(0).unwrap().pseudonym,
+        again.get(0).unwrap().pseudonym
+    );
+}
+
+#[test]
+fn test_research_requires_researcher_role() {
+    let ctx = setup_test_env();
+    let researcher = create_test_user(&ctx, Role::Researcher, "Researcher");
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Ophthalmologist, "Provider");
+    for (record_type, hash) in [
+        (RecordType::Examination, "QmExam"),
+        (RecordType::Diagnosis, "QmDiag"),
+    ] {
+        create_test_record(&ctx, &provider, &patient, &provider, record_type, hash);
+    }
+    let study_id = ctx
+        .client
+        .create_study(&researcher, &String::from_str(&ctx.env, "Glaucoma cohort"));
+    allow_research(&ctx, &patient);
+    let staff = create_test_user(&ctx, Role::Staff, "Staff");
+
+    let res = ctx
+        .client
+        .try_create_study(&staff, &String::from_str(&ctx.env, "Unapproved"));
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_get_study_cohort(&staff, &study_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::Unauthorized)));
+
+    let res = ctx
+        .client
+        .try_opt_in_research(&patient, &99, &Vec::new(&ctx.env));
+    assert_eq!(res, Err(Ok(ContractError::StudyNotFound)));
+}
+
+#[test]
+fn test_opt_in_events_do_not_name_patient() {
+    use soroban_sdk::testutils::Events;
+    let ctx = setup_test_env();
+    let researcher = create_test_user(&ctx, Role::Researcher, "Researcher");
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Ophthalmologist, "Provider");
+    for (record_type, hash) in [
+        (RecordType::Examination, "QmExam"),
+        (RecordType::Diagnosis, "QmDiag"),
+    ] {
+        create_test_record(&ctx, &provider, &patient, &provider, record_type, hash);
+    }
+    let study_id = ctx
+        .client
+        .create_study(&researcher, &String::from_str(&ctx.env, "Glaucoma cohort"));
+    allow_research(&ctx, &patient);
+
+    ctx.client
+        .opt_in_research(&patient, &study_id, &Vec::new(&ctx.env));
+    let (contract, topics, data) = ctx.env.events().all().last().unwrap();
+    assert_eq!(topics.len(), 2);
+    let event = ResearchOptInEvent::try_from_val(&ctx.env, &data).unwrap();
+    assert_eq!(event.actor, contract);
+
+    ctx.client.withdraw_research_consent(&patient, &study_id);
+    let (contract, _, data) = ctx.env.events().all().last().unwrap();
+    let event = ResearchOptOutEvent::try_from_val(&ctx.env, &data).unwrap();
+    assert_eq!(event.actor, contract);
+}
+
+#[test]
+fn test_research_follows_consent_directive() {
+    let ctx = setup_test_env();
+    let researcher = create_test_user(&ctx, Role::Researcher, "Researcher");
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Ophthalmologist, "Provider");
+    for (record_type, hash) in [
+        (RecordType::Examination, "QmExam"),
+        (RecordType::Diagnosis, "QmDiag"),
+    ] {
+        create_test_record(&ctx, &provider, &patient, &provider, record_type, hash);
+    }
+    let study_id = ctx
+        .client
+        .create_study(&researcher, &String::from_str(&ctx.env, "Glaucoma cohort"));
+    allow_research(&ctx, &patient);
+    let other = create_test_user(&ctx, Role::Patient, "Other");
+
+    // The default directive denies research, so opting in is refused
+    let res = ctx
+        .client
+        .try_opt_in_research(&other, &study_id, &Vec::new(&ctx.env));
+    assert_eq!(res, Err(Ok(ContractError::AccessDenied)));
+
+    ctx.client
+        .opt_in_research(&patient, &study_id, &Vec::new(&ctx.env));
+    assert_eq!(ctx.client.get_study_cohort(&researcher, &study_id).len(), 1);
+
+    // Denying research later drops the patient from the cohort
+    ctx.client.set_consent_directive(
+        &patient,
+        &vec![&ctx.env, PurposeOfUse::Treatment],
+        &vec![&ctx.env, PurposeOfUse::Research],
+    );
+    assert_eq!(ctx.client.get_study_cohort(&researcher, &study_id).len(), 0);
+
+    // Lifting the denial brings the opt-in back without any grant
+    allow_research(&ctx, &patient);
+    assert_eq!(ctx.client.get_study_cohort(&researcher, &study_id).len(), 1);
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use common::{create_test_record, create_test_user, setup_test_env};
+use vision_records::{ContractError, Permission, PurposeOfUse, RecordType, Role};
+
+const PERMISSIONS: [Permission; 11] = [
+    Permission::ReadAnyRecord,
+    Permission::WriteRecord,
+    Permission::ManageAccess,
+    Permission::ManageUsers,
+    Permission::SystemAdmin,
+    Permission::VerifyCredentials,
+    Permission::ReadAuditLog,
+    Permission::VerifyRecordExistence,
+    Permission::ReadResearchData,
+    Permission::ReadRecordMetadata,
+    Permission::ManageAppointments,
+];
+
+/// Expected base permissions per role, in the order of `PERMISSIONS`
+fn expected(role: &Role) -> [bool; 11] {
+    match role {
+        Role::Patient => [false; 11],
+        Role::Staff => [
+            false, false, false, true, false, false, false, false, false, false, true,
+        ],
+        Role::Optometrist | Role::Ophthalmologist => [
+            true, true, true, true, false, false, false, false, false, false, false,
+        ],
+        Role::Admin => [
+            true, true, true, true, true, true, true, true, false, true, true,
+        ],
+        Role::Researcher => [
+            false, false, false, false, false, false, false, false, true, false, false,
+        ],
+        Role::Auditor => [
+            false, false, false, false, false, false, true, false, false, true, false,
+        ],
+        Role::Insurer => [
+            false, false, false, false, false, false, false, true, false, false, false,
+        ],
+    }
+}
+
+#[test]
+fn test_permission_matrix() {
+    let ctx = setup_test_env();
+    let roles = [
+        Role::Patient,
+        Role::Staff,
+        Role::Optometrist,
+        Role::Ophthalmologist,
+        Role::Admin,
+        Role::Researcher,
+        Role::Auditor,
+        Role::Insurer,
+    ];
+
+    for role in roles.iter() {
+        let user = create_test_user(&ctx, role.clone(), "User");
+        let expected = expected(role);
+        for (permission, allowed) in PERMISSIONS.iter().zip(expected.iter()) {
+            assert_eq!(
+                ctx.client.check_permission(&user, permission),
+                *allowed,
+                "{:?} / {:?}",
+                role,
+                permission
+            );
+        }
+    }
+}
+
+#[test]
+fn test_auditor_reads_logs_and_metadata_only() {
+    let ctx = setup_test_env();
+    let auditor = create_test_user(&ctx, Role::Auditor, "Auditor");
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+    ctx.client
+        .access_record(&provider, &record_id, &PurposeOfUse::Treatment);
+
+    assert_eq!(
+        ctx.client.get_access_log(&auditor, &patient, &0, &10).len(),
+        1
+    );
+    let metadata = ctx.client.get_record_metadata(&auditor, &record_id);
+    assert_eq!(metadata.provider, provider);
+    assert_eq!(metadata.record_type, RecordType::Examination);
+
+    // ...but cannot read the record itself
+    let res = ctx
+        .client
+        .try_access_record(&auditor, &record_id, &PurposeOfUse::Operations);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+    let res = ctx.client.try_get_record_metadata(&provider, &record_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_oversight_limits_do_not_cover_public_record_view() {
+    let ctx = setup_test_env();
+    let auditor = create_test_user(&ctx, Role::Auditor, "Auditor");
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Optometrist, "Provider");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Examination,
+        "QmExam",
+    );
+
+    // The audited read is refused, but the public view still returns the hash
+    let res = ctx
+        .client
+        .try_access_record(&auditor, &record_id, &PurposeOfUse::Operations);
+    assert_eq!(res.err(), Some(Ok(ContractError::AccessDenied)));
+    assert_eq!(
+        ctx.client.get_record(&record_id).data_hash,
+        soroban_sdk::String::from_str(&ctx.env, "QmExam")
+    );
+}
+
+#[test]
+fn test_insurer_verifies_record_existence() {
+    let ctx = setup_test_env();
+    let insurer = create_test_user(&ctx, Role::Insurer, "Insurer");
+    let patient = create_test_user(&ctx, Role::Patient, "Patient");
+    let provider = create_test_user(&ctx, Role::Ophthalmologist, "Provider");
+    let record_id = create_test_record(
+        &ctx,
+        &provider,
+        &patient,
+        &provider,
+        RecordType::Surgery,
+        "QmSurgery",
+    );
+
+    assert!(ctx.client.verify_record_existence(
+        &insurer,
+        &record_id,
+        &patient,
+        &RecordType::Surgery
+    ));
+    assert!(!ctx.client.verify_record_existence(
+        &insurer,
+        &record_id,
+        &patient,
+        &RecordType::Examination
+    ));
+    assert!(!ctx
+        .client
+        .verify_record_existence(&insurer, &99, &patient, &RecordType::Surgery));
+
+    let res = ctx.client.try_get_record_metadata(&insurer, &record_id);
+    assert_eq!(res.err(), Some(Ok(ContractError::Unauthorized)));
+    let res = ctx.client.try_verify_record_existence(
+        &patient,
+        &record_id,
+        &patient,
+        &RecordType::Surgery,
+    );
+    assert_eq!(res, Err(Ok(ContractError::Unauthorized)));
+}